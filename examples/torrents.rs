@@ -1,5 +1,5 @@
 use anyhow::Result;
-use qbit_api_rs::client::QbitClient;
+use qbit_api_rs::client::{QbitApi, QbitClient};
 use qbit_api_rs::types;
 
 #[tokio::main]
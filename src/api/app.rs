@@ -1,4 +1,4 @@
-use super::Endpoint;
+use super::{de_json, Endpoint};
 use crate::error::ClientError;
 use crate::types;
 use async_trait::async_trait;
@@ -13,7 +13,7 @@ impl Endpoint for Version {
     type Query = ();
     type Form = ();
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/app/version".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -21,9 +21,8 @@ impl Endpoint for Version {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -39,7 +38,7 @@ impl Endpoint for WebApiVersion {
     type Query = ();
     type Form = ();
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/app/webapiVersion".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -47,9 +46,8 @@ impl Endpoint for WebApiVersion {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -65,7 +63,7 @@ impl Endpoint for BuildInfo {
     type Query = ();
     type Form = ();
     type Response = types::app::BuildInfoResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/app/buildInfo".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -73,13 +71,12 @@ impl Endpoint for BuildInfo {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::app::BuildInfoResponse>().await?)
+        de_json::<types::app::BuildInfoResponse>(res).await
     }
 }
 
@@ -91,7 +88,7 @@ impl Endpoint for Shutdown {
     type Query = ();
     type Form = ();
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/app/shutdown".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -99,9 +96,8 @@ impl Endpoint for Shutdown {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -117,7 +113,7 @@ impl Endpoint for Preferences {
     type Query = ();
     type Form = ();
     type Response = types::app::Preferences;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/app/preferences".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -125,13 +121,12 @@ impl Endpoint for Preferences {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::app::Preferences>().await?)
+        de_json::<types::app::Preferences>(res).await
     }
 }
 
@@ -145,7 +140,7 @@ impl Endpoint for SetPreferences {
     type Query = ();
     type Form = types::app::SetPreferencesForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/app/setPreferences".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -156,9 +151,8 @@ impl Endpoint for SetPreferences {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -174,7 +168,7 @@ impl Endpoint for DefaultSavePath {
     type Query = ();
     type Form = ();
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/app/defaultSavePath".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -182,9 +176,8 @@ impl Endpoint for DefaultSavePath {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
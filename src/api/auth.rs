@@ -15,7 +15,7 @@ impl Endpoint for Login {
     type Query = ();
     type Form = types::auth::LoginForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/auth/login".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -26,9 +26,8 @@ impl Endpoint for Login {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::Authentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -44,7 +43,7 @@ impl Endpoint for Logout {
     type Query = ();
     type Form = ();
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/auth/logout".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -52,9 +51,8 @@ impl Endpoint for Logout {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1,4 +1,4 @@
-use super::Endpoint;
+use super::{de_json, Endpoint};
 use crate::error::ClientError;
 use crate::types;
 use async_trait::async_trait;
@@ -15,7 +15,7 @@ impl Endpoint for Main {
     type Query = types::log::MainQuery;
     type Form = ();
     type Response = types::log::MainResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/log/main".into()
     }
     fn query(&self) -> Option<&Self::Query> {
@@ -26,13 +26,12 @@ impl Endpoint for Main {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::log::MainResponse>().await?)
+        de_json::<types::log::MainResponse>(res).await
     }
 }
 
@@ -46,7 +45,7 @@ impl Endpoint for Peers {
     type Query = types::log::PeersQuery;
     type Form = ();
     type Response = types::log::PeersResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/log/peers".into()
     }
     fn query(&self) -> Option<&Self::Query> {
@@ -57,12 +56,11 @@ impl Endpoint for Peers {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::log::PeersResponse>().await?)
+        de_json::<types::log::PeersResponse>(res).await
     }
 }
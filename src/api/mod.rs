@@ -1,12 +1,13 @@
 pub mod app;
 pub mod auth;
 pub mod log;
+pub mod rss;
 pub mod search;
 pub mod sync;
 pub mod torrents;
 pub mod transfer;
 
-use crate::error::ClientError;
+use crate::error::{truncate_body, ClientError};
 use async_trait::async_trait;
 use serde::{de::DeserializeOwned, Serialize};
 use std::borrow::Cow;
@@ -16,9 +17,10 @@ pub trait Endpoint {
     /// define type of query, form and response
     type Query: Serialize;
     type Form: Serialize;
-    type Response: DeserializeOwned;
-    /// The endpoint relative path. Must start with a `/`
-    fn relative_path(&self) -> Cow<str>;
+    type Response: DeserializeOwned + Send;
+    /// The endpoint relative path. Must start with a `/`.
+    /// It is resolved against the path of host, see [`crate::client::QbitClient::host`]
+    fn relative_path(&self) -> Cow<'_, str>;
     /// The query to be used when calling this endpoint.
     fn query(&self) -> Option<&Self::Query> {
         None
@@ -35,8 +37,30 @@ pub trait Endpoint {
     fn method(&self) -> reqwest::Method {
         reqwest::Method::POST
     }
-    /// Check the status code
+    /// Whether sending the request more than once has the same effect as sending it once,
+    /// only idempotent endpoints are retried by default, see [`crate::client::RetryPolicy`]
+    fn is_idempotent(&self) -> bool {
+        self.method() == reqwest::Method::GET
+    }
+    /// Map the error statuses documented for this endpoint to errors
+    ///
+    /// return `None` for any other status. an undocumented status which is not a success
+    /// becomes [`ClientError::UnexpectedStatus`]
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError>;
     /// Deserialize the response
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError>;
 }
+
+/// Deserialize a json response, the endpoint and body are kept in the error if it fails
+pub async fn de_json<T>(res: reqwest::Response) -> Result<T, ClientError>
+where
+    T: DeserializeOwned,
+{
+    let endpoint = res.url().path().to_owned();
+    let body = res.text().await?;
+    serde_json::from_str(&body).map_err(|e| ClientError::ParseError {
+        endpoint,
+        body: truncate_body(&body),
+        source: Box::new(e),
+    })
+}
@@ -0,0 +1,377 @@
+use super::{de_json, Endpoint};
+use crate::error::ClientError;
+use crate::types;
+use async_trait::async_trait;
+use reqwest::{Method, StatusCode};
+use std::borrow::Cow;
+
+/// # `/api/v2/rss/addFolder`
+pub struct AddFolder {
+    pub f: types::rss::AddFolderForm,
+}
+
+#[async_trait]
+impl Endpoint for AddFolder {
+    type Query = ();
+    type Form = types::rss::AddFolderForm;
+    type Response = String;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/rss/addFolder".into()
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::POST
+    }
+    fn form(&self) -> Option<&Self::Form> {
+        Some(&self.f)
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            StatusCode::CONFLICT => Some(ClientError::Conflict(format!(
+                "Failure to add folder {}",
+                self.f.path
+            ))),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        Ok(res.text().await?)
+    }
+}
+
+/// # `/api/v2/rss/addFeed`
+pub struct AddFeed {
+    pub f: types::rss::AddFeedForm,
+}
+
+#[async_trait]
+impl Endpoint for AddFeed {
+    type Query = ();
+    type Form = types::rss::AddFeedForm;
+    type Response = String;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/rss/addFeed".into()
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::POST
+    }
+    fn form(&self) -> Option<&Self::Form> {
+        Some(&self.f)
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            StatusCode::CONFLICT => Some(ClientError::Conflict(format!(
+                "Failure to add feed {}",
+                self.f.url
+            ))),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        Ok(res.text().await?)
+    }
+}
+
+/// # `/api/v2/rss/removeItem`
+pub struct RemoveItem {
+    pub f: types::rss::RemoveItemForm,
+}
+
+#[async_trait]
+impl Endpoint for RemoveItem {
+    type Query = ();
+    type Form = types::rss::RemoveItemForm;
+    type Response = String;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/rss/removeItem".into()
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::POST
+    }
+    fn form(&self) -> Option<&Self::Form> {
+        Some(&self.f)
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            StatusCode::CONFLICT => Some(ClientError::Conflict(format!(
+                "Failure to remove item {}",
+                self.f.path
+            ))),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        Ok(res.text().await?)
+    }
+}
+
+/// # `/api/v2/rss/moveItem`
+pub struct MoveItem {
+    pub f: types::rss::MoveItemForm,
+}
+
+#[async_trait]
+impl Endpoint for MoveItem {
+    type Query = ();
+    type Form = types::rss::MoveItemForm;
+    type Response = String;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/rss/moveItem".into()
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::POST
+    }
+    fn form(&self) -> Option<&Self::Form> {
+        Some(&self.f)
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            StatusCode::CONFLICT => Some(ClientError::Conflict(format!(
+                "Failure to move item {} to {}",
+                self.f.item_path, self.f.dest_path
+            ))),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        Ok(res.text().await?)
+    }
+}
+
+/// # `/api/v2/rss/items`
+pub struct Items {
+    pub q: types::rss::ItemsQuery,
+}
+
+#[async_trait]
+impl Endpoint for Items {
+    type Query = types::rss::ItemsQuery;
+    type Form = ();
+    type Response = types::rss::ItemsResponse;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/rss/items".into()
+    }
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.q)
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::GET
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        de_json::<types::rss::ItemsResponse>(res).await
+    }
+}
+
+/// # `/api/v2/rss/markAsRead`
+pub struct MarkAsRead {
+    pub f: types::rss::MarkAsReadForm,
+}
+
+#[async_trait]
+impl Endpoint for MarkAsRead {
+    type Query = ();
+    type Form = types::rss::MarkAsReadForm;
+    type Response = String;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/rss/markAsRead".into()
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::POST
+    }
+    fn form(&self) -> Option<&Self::Form> {
+        Some(&self.f)
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        Ok(res.text().await?)
+    }
+}
+
+/// # `/api/v2/rss/refreshItem`
+pub struct RefreshItem {
+    pub f: types::rss::RefreshItemForm,
+}
+
+#[async_trait]
+impl Endpoint for RefreshItem {
+    type Query = ();
+    type Form = types::rss::RefreshItemForm;
+    type Response = String;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/rss/refreshItem".into()
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::POST
+    }
+    fn form(&self) -> Option<&Self::Form> {
+        Some(&self.f)
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        Ok(res.text().await?)
+    }
+}
+
+/// # `/api/v2/rss/setRule`
+pub struct SetRule {
+    pub f: types::rss::SetRuleForm,
+}
+
+#[async_trait]
+impl Endpoint for SetRule {
+    type Query = ();
+    type Form = types::rss::SetRuleForm;
+    type Response = String;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/rss/setRule".into()
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::POST
+    }
+    fn form(&self) -> Option<&Self::Form> {
+        Some(&self.f)
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        Ok(res.text().await?)
+    }
+}
+
+/// # `/api/v2/rss/renameRule`
+pub struct RenameRule {
+    pub f: types::rss::RenameRuleForm,
+}
+
+#[async_trait]
+impl Endpoint for RenameRule {
+    type Query = ();
+    type Form = types::rss::RenameRuleForm;
+    type Response = String;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/rss/renameRule".into()
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::POST
+    }
+    fn form(&self) -> Option<&Self::Form> {
+        Some(&self.f)
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        Ok(res.text().await?)
+    }
+}
+
+/// # `/api/v2/rss/removeRule`
+pub struct RemoveRule {
+    pub f: types::rss::RemoveRuleForm,
+}
+
+#[async_trait]
+impl Endpoint for RemoveRule {
+    type Query = ();
+    type Form = types::rss::RemoveRuleForm;
+    type Response = String;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/rss/removeRule".into()
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::POST
+    }
+    fn form(&self) -> Option<&Self::Form> {
+        Some(&self.f)
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        Ok(res.text().await?)
+    }
+}
+
+/// # `/api/v2/rss/rules`
+pub struct Rules;
+
+#[async_trait]
+impl Endpoint for Rules {
+    type Query = ();
+    type Form = ();
+    type Response = types::rss::RulesResponse;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/rss/rules".into()
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::GET
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        de_json::<types::rss::RulesResponse>(res).await
+    }
+}
+
+/// # `/api/v2/rss/matchingArticles`
+pub struct MatchingArticles {
+    pub q: types::rss::MatchingArticlesQuery,
+}
+
+#[async_trait]
+impl Endpoint for MatchingArticles {
+    type Query = types::rss::MatchingArticlesQuery;
+    type Form = ();
+    type Response = types::rss::MatchingArticlesResponse;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/rss/matchingArticles".into()
+    }
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.q)
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::GET
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        de_json::<types::rss::MatchingArticlesResponse>(res).await
+    }
+}
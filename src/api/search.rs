@@ -1,4 +1,4 @@
-use super::Endpoint;
+use super::{de_json, Endpoint};
 use crate::error::ClientError;
 use crate::types;
 use async_trait::async_trait;
@@ -15,7 +15,7 @@ impl Endpoint for Start {
     type Query = ();
     type Form = types::search::StartForm;
     type Response = types::search::StartResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/search/start".into()
     }
     fn form(&self) -> Option<&Self::Form> {
@@ -26,16 +26,15 @@ impl Endpoint for Start {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::CONFLICT => Some(ClientError::Conflict(
                 "User has reached the limit of max Running searches (currently set to 5)".into(),
             )),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::search::StartResponse>().await?)
+        de_json::<types::search::StartResponse>(res).await
     }
 }
 
@@ -49,7 +48,7 @@ impl Endpoint for Stop {
     type Query = ();
     type Form = types::search::StopForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/search/stop".into()
     }
     fn form(&self) -> Option<&Self::Form> {
@@ -60,10 +59,9 @@ impl Endpoint for Stop {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::SearchJobNotFound { id: self.f.id }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -81,7 +79,7 @@ impl Endpoint for Status {
     type Query = types::search::StatusQuery;
     type Form = ();
     type Response = types::search::StatusResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/search/status".into()
     }
     fn query(&self) -> Option<&Self::Query> {
@@ -92,16 +90,13 @@ impl Endpoint for Status {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            StatusCode::NOT_FOUND => Some(ClientError::SearchJobNotFound {
-                id: self.q.id.unwrap(),
-            }),
-            _ => Some(ClientError::Unknown),
+            StatusCode::NOT_FOUND => self.q.id.map(|id| ClientError::SearchJobNotFound { id }),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::search::StatusResponse>().await?)
+        de_json::<types::search::StatusResponse>(res).await
     }
 }
 
@@ -115,7 +110,7 @@ impl Endpoint for Results {
     type Query = types::search::ResultsQuery;
     type Form = ();
     type Response = types::search::ResultsResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/search/results".into()
     }
     fn query(&self) -> Option<&Self::Query> {
@@ -126,17 +121,16 @@ impl Endpoint for Results {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::SearchJobNotFound { id: self.q.id }),
             StatusCode::CONFLICT => Some(ClientError::Conflict(
                 "Offset is too large or too small".into(),
             )),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::search::ResultsResponse>().await?)
+        de_json::<types::search::ResultsResponse>(res).await
     }
 }
 
@@ -150,7 +144,7 @@ impl Endpoint for Delete {
     type Query = ();
     type Form = types::search::DeleteForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/search/delete".into()
     }
     fn form(&self) -> Option<&Self::Form> {
@@ -161,10 +155,149 @@ impl Endpoint for Delete {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::SearchJobNotFound { id: self.f.id }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        Ok(res.text().await?)
+    }
+}
+
+/// # `/api/v2/search/plugins`
+pub struct Plugins;
+
+#[async_trait]
+impl Endpoint for Plugins {
+    type Query = ();
+    type Form = ();
+    type Response = types::search::PluginsResponse;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/search/plugins".into()
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::GET
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        de_json::<types::search::PluginsResponse>(res).await
+    }
+}
+
+/// # `/api/v2/search/installPlugin`
+pub struct InstallPlugin {
+    pub f: types::search::InstallPluginForm,
+}
+
+#[async_trait]
+impl Endpoint for InstallPlugin {
+    type Query = ();
+    type Form = types::search::InstallPluginForm;
+    type Response = String;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/search/installPlugin".into()
+    }
+    fn form(&self) -> Option<&Self::Form> {
+        Some(&self.f)
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::POST
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        Ok(res.text().await?)
+    }
+}
+
+/// # `/api/v2/search/uninstallPlugin`
+pub struct UninstallPlugin {
+    pub f: types::search::UninstallPluginForm,
+}
+
+#[async_trait]
+impl Endpoint for UninstallPlugin {
+    type Query = ();
+    type Form = types::search::UninstallPluginForm;
+    type Response = String;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/search/uninstallPlugin".into()
+    }
+    fn form(&self) -> Option<&Self::Form> {
+        Some(&self.f)
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::POST
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        Ok(res.text().await?)
+    }
+}
+
+/// # `/api/v2/search/enablePlugin`
+pub struct EnablePlugin {
+    pub f: types::search::EnablePluginForm,
+}
+
+#[async_trait]
+impl Endpoint for EnablePlugin {
+    type Query = ();
+    type Form = types::search::EnablePluginForm;
+    type Response = String;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/search/enablePlugin".into()
+    }
+    fn form(&self) -> Option<&Self::Form> {
+        Some(&self.f)
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::POST
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        Ok(res.text().await?)
+    }
+}
+
+/// # `/api/v2/search/updatePlugins`
+pub struct UpdatePlugins;
+
+#[async_trait]
+impl Endpoint for UpdatePlugins {
+    type Query = ();
+    type Form = ();
+    type Response = String;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/search/updatePlugins".into()
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::POST
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
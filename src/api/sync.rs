@@ -1,4 +1,4 @@
-use super::Endpoint;
+use super::{de_json, Endpoint};
 use crate::error::ClientError;
 use crate::types;
 use async_trait::async_trait;
@@ -15,7 +15,7 @@ impl Endpoint for Maindata {
     type Query = types::sync::MaindataQuery;
     type Form = ();
     type Response = types::sync::MaindataResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/sync/maindata".into()
     }
     fn query(&self) -> Option<&Self::Query> {
@@ -24,15 +24,17 @@ impl Endpoint for Maindata {
     fn method(&self) -> reqwest::Method {
         Method::POST
     }
+    fn is_idempotent(&self) -> bool {
+        true
+    }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::sync::MaindataResponse>().await?)
+        de_json::<types::sync::MaindataResponse>(res).await
     }
 }
 
@@ -46,7 +48,7 @@ impl Endpoint for TorrentPeers {
     type Query = types::sync::TorrentPeersQuery;
     type Form = ();
     type Response = types::sync::TorrentPeersResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/sync/torrentPeers".into()
     }
     fn query(&self) -> Option<&Self::Query> {
@@ -57,15 +59,14 @@ impl Endpoint for TorrentPeers {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.q.hash.clone(),
             }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::sync::TorrentPeersResponse>().await?)
+        de_json::<types::sync::TorrentPeersResponse>(res).await
     }
 }
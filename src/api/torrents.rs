@@ -3,7 +3,7 @@ use crate::types;
 use async_trait::async_trait;
 use reqwest::{Method, StatusCode};
 use std::borrow::Cow;
-use super::Endpoint;
+use super::{de_json, Endpoint};
 
 /// # `/api/v2/torrents/info`
 pub struct Info {
@@ -15,7 +15,7 @@ impl Endpoint for Info {
     type Query = types::torrents::InfoQuery;
     type Form = ();
     type Response = types::torrents::InfoResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/info".into()
     }
     fn query(&self) -> Option<&Self::Query> {
@@ -26,13 +26,12 @@ impl Endpoint for Info {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::torrents::InfoResponse>().await?)
+        de_json::<types::torrents::InfoResponse>(res).await
     }
 }
 
@@ -46,7 +45,7 @@ impl Endpoint for Properties {
     type Query = types::torrents::PropertiesQuery;
     type Form = ();
     type Response = types::torrents::PropertiesResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/properties".into()
     }
     fn query(&self) -> Option<&Self::Query> {
@@ -57,16 +56,15 @@ impl Endpoint for Properties {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.q.hash.clone(),
             }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::torrents::PropertiesResponse>().await?)
+        de_json::<types::torrents::PropertiesResponse>(res).await
     }
 }
 
@@ -80,7 +78,7 @@ impl Endpoint for Trackers {
     type Query = types::torrents::TrackersQuery;
     type Form = ();
     type Response = types::torrents::TrackersResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/trackers".into()
     }
     fn query(&self) -> Option<&Self::Query> {
@@ -91,16 +89,15 @@ impl Endpoint for Trackers {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.q.hash.clone(),
             }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::torrents::TrackersResponse>().await?)
+        de_json::<types::torrents::TrackersResponse>(res).await
     }
 }
 
@@ -114,7 +111,7 @@ impl Endpoint for Webseeds {
     type Query = types::torrents::WebseedsQuery;
     type Form = ();
     type Response = types::torrents::WebseedsResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/webseeds".into()
     }
     fn query(&self) -> Option<&Self::Query> {
@@ -125,16 +122,15 @@ impl Endpoint for Webseeds {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.q.hash.clone(),
             }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::torrents::WebseedsResponse>().await?)
+        de_json::<types::torrents::WebseedsResponse>(res).await
     }
 }
 
@@ -148,7 +144,7 @@ impl Endpoint for Files {
     type Query = types::torrents::FilesQuery;
     type Form = ();
     type Response = types::torrents::FilesResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/files".into()
     }
     fn query(&self) -> Option<&Self::Query> {
@@ -159,16 +155,15 @@ impl Endpoint for Files {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.q.hash.clone(),
             }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::torrents::FilesResponse>().await?)
+        de_json::<types::torrents::FilesResponse>(res).await
     }
 }
 
@@ -182,7 +177,7 @@ impl Endpoint for PieceStates {
     type Query = types::torrents::PieceStatesQuery;
     type Form = ();
     type Response = types::torrents::PieceStatesResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/pieceStates".into()
     }
     fn query(&self) -> Option<&Self::Query> {
@@ -193,16 +188,15 @@ impl Endpoint for PieceStates {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.q.hash.clone(),
             }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::torrents::PieceStatesResponse>().await?)
+        de_json::<types::torrents::PieceStatesResponse>(res).await
     }
 }
 
@@ -216,7 +210,7 @@ impl Endpoint for PieceHashes {
     type Query = types::torrents::PieceHashesQuery;
     type Form = ();
     type Response = types::torrents::PieceHashesResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/pieceHashes".into()
     }
     fn query(&self) -> Option<&Self::Query> {
@@ -227,16 +221,15 @@ impl Endpoint for PieceHashes {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.q.hash.clone(),
             }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::torrents::PieceHashesResponse>().await?)
+        de_json::<types::torrents::PieceHashesResponse>(res).await
     }
 }
 
@@ -250,7 +243,7 @@ impl Endpoint for Pause {
     type Query = ();
     type Form = types::torrents::PauseForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/pause".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -261,9 +254,8 @@ impl Endpoint for Pause {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -281,7 +273,7 @@ impl Endpoint for Resume {
     type Query = ();
     type Form = types::torrents::ResumeForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/resume".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -292,9 +284,8 @@ impl Endpoint for Resume {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -312,7 +303,7 @@ impl Endpoint for Delete {
     type Query = ();
     type Form = types::torrents::DeleteForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/delete".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -323,9 +314,8 @@ impl Endpoint for Delete {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -343,7 +333,7 @@ impl Endpoint for Recheck {
     type Query = ();
     type Form = types::torrents::RecheckForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/recheck".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -354,9 +344,8 @@ impl Endpoint for Recheck {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -374,7 +363,7 @@ impl Endpoint for Reannounce {
     type Query = ();
     type Form = types::torrents::ReannounceForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/reannounce".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -385,9 +374,8 @@ impl Endpoint for Reannounce {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -405,7 +393,7 @@ impl Endpoint for Add {
     type Query = ();
     type Form = ();
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/add".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -416,7 +404,6 @@ impl Endpoint for Add {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::UNSUPPORTED_MEDIA_TYPE => Some(ClientError::TorrentFileInvalid {
                 path: {
@@ -424,7 +411,7 @@ impl Endpoint for Add {
                     paths.join(", ")
                 },
             }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -442,7 +429,7 @@ impl Endpoint for AddTrackers {
     type Query = ();
     type Form = types::torrents::AddTrackersForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/addTrackers".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -453,12 +440,11 @@ impl Endpoint for AddTrackers {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.f.hash.clone(),
             }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -476,7 +462,7 @@ impl Endpoint for EditTracker {
     type Query = ();
     type Form = types::torrents::EditTrackerForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/editTracker".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -487,7 +473,6 @@ impl Endpoint for EditTracker {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::BAD_REQUEST => Some(ClientError::BadRequest(format!(
                 "{} is not a valid URL",
@@ -500,7 +485,7 @@ impl Endpoint for EditTracker {
                 "{} not found or {} already exists.",
                 self.f.orig_url, self.f.new_url
             ))),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -518,7 +503,7 @@ impl Endpoint for RemoveTrackers {
     type Query = ();
     type Form = types::torrents::RemoveTrackersForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/removeTrackers".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -529,7 +514,6 @@ impl Endpoint for RemoveTrackers {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.f.hash.clone(),
@@ -538,7 +522,7 @@ impl Endpoint for RemoveTrackers {
                 "all URL ({}) not found.",
                 self.f.urls.join(", ")
             ))),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -556,7 +540,7 @@ impl Endpoint for AddPeers {
     type Query = ();
     type Form = types::torrents::AddPeersForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/addPeers".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -567,7 +551,6 @@ impl Endpoint for AddPeers {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::BAD_REQUEST => Some(ClientError::BadRequest(format!(
                 "All peers ({}) are not valid",
@@ -577,7 +560,7 @@ impl Endpoint for AddPeers {
                 hash: self.f.hashes.join(", "),
             }),
 
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -595,7 +578,7 @@ impl Endpoint for IncreasePrio {
     type Query = ();
     type Form = types::torrents::IncreasePrioForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/increasePrio".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -606,7 +589,6 @@ impl Endpoint for IncreasePrio {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.f.hashes.join(", "),
@@ -614,7 +596,7 @@ impl Endpoint for IncreasePrio {
             StatusCode::CONFLICT => Some(ClientError::Conflict(
                 "Torrent queueing is not enabled.".to_string(),
             )),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -632,7 +614,7 @@ impl Endpoint for DecreasePrio {
     type Query = ();
     type Form = types::torrents::DecreasePrioForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/decreasePrio".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -643,7 +625,6 @@ impl Endpoint for DecreasePrio {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.f.hashes.join(", "),
@@ -651,7 +632,7 @@ impl Endpoint for DecreasePrio {
             StatusCode::CONFLICT => Some(ClientError::Conflict(
                 "Torrent queueing is not enabled.".to_string(),
             )),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -669,7 +650,7 @@ impl Endpoint for TopPrio {
     type Query = ();
     type Form = types::torrents::TopPrioForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/topPrio".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -680,7 +661,6 @@ impl Endpoint for TopPrio {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.f.hashes.join(", "),
@@ -688,7 +668,7 @@ impl Endpoint for TopPrio {
             StatusCode::CONFLICT => Some(ClientError::Conflict(
                 "Torrent queueing is not enabled.".to_string(),
             )),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -706,7 +686,7 @@ impl Endpoint for BottomPrio {
     type Query = ();
     type Form = types::torrents::BottomPrioForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/bottomPrio".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -717,7 +697,6 @@ impl Endpoint for BottomPrio {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.f.hashes.join(", "),
@@ -725,7 +704,7 @@ impl Endpoint for BottomPrio {
             StatusCode::CONFLICT => Some(ClientError::Conflict(
                 "Torrent queueing is not enabled.".to_string(),
             )),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -734,7 +713,43 @@ impl Endpoint for BottomPrio {
 }
 
 /// # `/api/v2/torrents/filePrio`
-// TODO: Implement
+pub struct FilePrio {
+    pub f: types::torrents::FilePrioForm,
+}
+
+#[async_trait]
+impl Endpoint for FilePrio {
+    type Query = ();
+    type Form = types::torrents::FilePrioForm;
+    type Response = String;
+    fn relative_path(&self) -> Cow<'_, str> {
+        "/api/v2/torrents/filePrio".into()
+    }
+    fn method(&self) -> reqwest::Method {
+        Method::POST
+    }
+    fn form(&self) -> Option<&Self::Form> {
+        Some(&self.f)
+    }
+    fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
+        match status {
+            StatusCode::BAD_REQUEST => Some(ClientError::InvalidFilePriority {
+                priority: self.f.priority.clone() as u8,
+            }),
+            StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
+            StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
+                hash: self.f.hash.clone(),
+            }),
+            StatusCode::CONFLICT => Some(ClientError::TorrentMetadataNotReady {
+                hash: self.f.hash.clone(),
+            }),
+            _ => None,
+        }
+    }
+    async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
+        Ok(res.text().await?)
+    }
+}
 
 /// # `/api/v2/torrents/downloadLimit`
 pub struct DownloadLimit {
@@ -746,27 +761,29 @@ impl Endpoint for DownloadLimit {
     type Query = ();
     type Form = types::torrents::DownloadLimitForm;
     type Response = types::torrents::DownloadLimitResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/downloadLimit".into()
     }
     fn method(&self) -> reqwest::Method {
         Method::POST
     }
+    fn is_idempotent(&self) -> bool {
+        true
+    }
     fn form(&self) -> Option<&Self::Form> {
         Some(&self.f)
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.f.hashes.join(", "),
             }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::torrents::DownloadLimitResponse>().await?)
+        de_json::<types::torrents::DownloadLimitResponse>(res).await
     }
 }
 
@@ -780,7 +797,7 @@ impl Endpoint for SetDownloadLimit {
     type Query = ();
     type Form = types::torrents::SetDownloadLimitForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/setDownloadLimit".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -791,12 +808,11 @@ impl Endpoint for SetDownloadLimit {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.f.hashes.join(", "),
             }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -814,7 +830,7 @@ impl Endpoint for SetShareLimits {
     type Query = ();
     type Form = types::torrents::SetShareLimitsForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/setShareLimits".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -825,12 +841,11 @@ impl Endpoint for SetShareLimits {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.f.hashes.join(", "),
             }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -848,27 +863,29 @@ impl Endpoint for UploadLimit {
     type Query = ();
     type Form = types::torrents::UploadLimitForm;
     type Response = types::torrents::UploadLimitResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/uploadLimit".into()
     }
     fn method(&self) -> reqwest::Method {
         Method::POST
     }
+    fn is_idempotent(&self) -> bool {
+        true
+    }
     fn form(&self) -> Option<&Self::Form> {
         Some(&self.f)
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.f.hashes.join(", "),
             }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::torrents::UploadLimitResponse>().await?)
+        de_json::<types::torrents::UploadLimitResponse>(res).await
     }
 }
 
@@ -882,7 +899,7 @@ impl Endpoint for SetUploadLimit {
     type Query = ();
     type Form = types::torrents::SetUploadLimitForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/setUploadLimit".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -893,12 +910,11 @@ impl Endpoint for SetUploadLimit {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.f.hashes.join(", "),
             }),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -916,7 +932,7 @@ impl Endpoint for SetLocation {
     type Query = ();
     type Form = types::torrents::SetLocationForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/setLocation".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -927,7 +943,6 @@ impl Endpoint for SetLocation {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::BAD_REQUEST => Some(ClientError::BadRequest("Save path is empty".into())),
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
@@ -936,7 +951,7 @@ impl Endpoint for SetLocation {
             StatusCode::CONFLICT => Some(ClientError::Conflict(
                 "Unable to create save path directory".into(),
             )),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -954,7 +969,7 @@ impl Endpoint for Rename {
     type Query = ();
     type Form = types::torrents::RenameForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/rename".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -965,13 +980,12 @@ impl Endpoint for Rename {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.f.hash.clone(),
             }),
             StatusCode::CONFLICT => Some(ClientError::Conflict("Torrent name is empty".into())),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -989,7 +1003,7 @@ impl Endpoint for SetCategory {
     type Query = ();
     type Form = types::torrents::SetCategoryForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/setCategory".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1000,7 +1014,6 @@ impl Endpoint for SetCategory {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::NOT_FOUND => Some(ClientError::TorrentNotFound {
                 hash: self.f.hashes.join(", "),
@@ -1008,7 +1021,7 @@ impl Endpoint for SetCategory {
             StatusCode::CONFLICT => {
                 Some(ClientError::Conflict("Category name does not exist".into()))
             }
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1024,21 +1037,23 @@ impl Endpoint for Categories {
     type Query = ();
     type Form = ();
     type Response = types::torrents::CategoriesResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/categories".into()
     }
     fn method(&self) -> reqwest::Method {
         Method::POST
     }
+    fn is_idempotent(&self) -> bool {
+        true
+    }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::torrents::CategoriesResponse>().await?)
+        de_json::<types::torrents::CategoriesResponse>(res).await
     }
 }
 
@@ -1052,7 +1067,7 @@ impl Endpoint for CreateCategory {
     type Query = ();
     type Form = types::torrents::CreateCategoryForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/createCategory".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1063,13 +1078,12 @@ impl Endpoint for CreateCategory {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::BAD_REQUEST => {
                 Some(ClientError::BadRequest("Category name is empty".into()))
             }
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::CONFLICT => Some(ClientError::Conflict("Category name is invalid".into())),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1087,7 +1101,7 @@ impl Endpoint for EditCategory {
     type Query = ();
     type Form = types::torrents::EditCategoryForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/editCategory".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1098,13 +1112,12 @@ impl Endpoint for EditCategory {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::BAD_REQUEST => {
                 Some(ClientError::BadRequest("Category name is empty".into()))
             }
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
             StatusCode::CONFLICT => Some(ClientError::Conflict("Category name is invalid".into())),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1122,7 +1135,7 @@ impl Endpoint for RemoveCategories {
     type Query = ();
     type Form = types::torrents::RemoveCategoriesForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/removeCategories".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1133,9 +1146,8 @@ impl Endpoint for RemoveCategories {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1153,7 +1165,7 @@ impl Endpoint for AddTags {
     type Query = ();
     type Form = types::torrents::AddTagsForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/addTags".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1164,9 +1176,8 @@ impl Endpoint for AddTags {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1184,7 +1195,7 @@ impl Endpoint for RemoveTags {
     type Query = ();
     type Form = types::torrents::RemoveTagsForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/removeTags".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1195,9 +1206,8 @@ impl Endpoint for RemoveTags {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1213,21 +1223,23 @@ impl Endpoint for Tags {
     type Query = ();
     type Form = ();
     type Response = types::torrents::TagsResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/tags".into()
     }
     fn method(&self) -> reqwest::Method {
         Method::POST
     }
+    fn is_idempotent(&self) -> bool {
+        true
+    }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::torrents::TagsResponse>().await?)
+        de_json::<types::torrents::TagsResponse>(res).await
     }
 }
 
@@ -1241,7 +1253,7 @@ impl Endpoint for CreateTags {
     type Query = ();
     type Form = types::torrents::CreateTagsForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/createTags".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1252,9 +1264,8 @@ impl Endpoint for CreateTags {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1272,7 +1283,7 @@ impl Endpoint for DeleteTags {
     type Query = ();
     type Form = types::torrents::DeleteTagsForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/deleteTags".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1283,9 +1294,8 @@ impl Endpoint for DeleteTags {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1303,7 +1313,7 @@ impl Endpoint for SetAutoManagement {
     type Query = ();
     type Form = types::torrents::SetAutoManagementForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/setAutoManagement".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1314,9 +1324,8 @@ impl Endpoint for SetAutoManagement {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1334,7 +1343,7 @@ impl Endpoint for ToggleSequentialDownload {
     type Query = ();
     type Form = types::torrents::ToggleSequentialDownloadForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/toggleSequentialDownload".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1345,9 +1354,8 @@ impl Endpoint for ToggleSequentialDownload {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1365,7 +1373,7 @@ impl Endpoint for ToggleFirstLastPiecePrio {
     type Query = ();
     type Form = types::torrents::ToggleFirstLastPiecePrioForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/toggleFirstLastPiecePrio".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1376,9 +1384,8 @@ impl Endpoint for ToggleFirstLastPiecePrio {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1396,7 +1403,7 @@ impl Endpoint for SetForceStart {
     type Query = ();
     type Form = types::torrents::SetForceStartForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/setForceStart".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1407,9 +1414,8 @@ impl Endpoint for SetForceStart {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1427,7 +1433,7 @@ impl Endpoint for SetSuperSeeding {
     type Query = ();
     type Form = types::torrents::SetSuperSeedingForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/setSuperSeeding".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1438,9 +1444,8 @@ impl Endpoint for SetSuperSeeding {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1458,7 +1463,7 @@ impl Endpoint for RenameFile {
     type Query = ();
     type Form = types::torrents::RenameFileForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/renameFile".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1469,7 +1474,6 @@ impl Endpoint for RenameFile {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::BAD_REQUEST => {
                 Some(ClientError::BadRequest("Missing new_path parameter".into()))
             }
@@ -1477,7 +1481,7 @@ impl Endpoint for RenameFile {
             StatusCode::CONFLICT => Some(ClientError::Conflict(
                 "Invalid new_path or old_path, or new_path already in use".into(),
             )),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1495,7 +1499,7 @@ impl Endpoint for RenameFolder {
     type Query = ();
     type Form = types::torrents::RenameFolderForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/torrents/renameFolder".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -1506,7 +1510,6 @@ impl Endpoint for RenameFolder {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::BAD_REQUEST => {
                 Some(ClientError::BadRequest("Missing new_path parameter".into()))
             }
@@ -1514,7 +1517,7 @@ impl Endpoint for RenameFolder {
             StatusCode::CONFLICT => Some(ClientError::Conflict(
                 "Invalid new_path or old_path, or new_path already in use".into(),
             )),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -1,4 +1,4 @@
-use super::Endpoint;
+use super::{de_json, Endpoint};
 use crate::error::ClientError;
 use crate::types;
 use async_trait::async_trait;
@@ -13,7 +13,7 @@ impl Endpoint for Info {
     type Query = ();
     type Form = ();
     type Response = types::transfer::InfoResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/transfer/info".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -21,13 +21,12 @@ impl Endpoint for Info {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
-        Ok(res.json::<types::transfer::InfoResponse>().await?)
+        de_json::<types::transfer::InfoResponse>(res).await
     }
 }
 
@@ -39,7 +38,7 @@ impl Endpoint for SpeedLimitsMode {
     type Query = ();
     type Form = ();
     type Response = types::transfer::SpeedLimitsModeResponse;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/transfer/speedLimitsMode".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -47,9 +46,8 @@ impl Endpoint for SpeedLimitsMode {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -67,7 +65,7 @@ impl Endpoint for ToggleSpeedLimitsMode {
     type Query = ();
     type Form = ();
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/transfer/toggleSpeedLimitsMode".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -75,9 +73,8 @@ impl Endpoint for ToggleSpeedLimitsMode {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -93,7 +90,7 @@ impl Endpoint for DownloadLimit {
     type Query = ();
     type Form = ();
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/transfer/downloadLimit".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -101,9 +98,8 @@ impl Endpoint for DownloadLimit {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -121,7 +117,7 @@ impl Endpoint for SetDownloadLimit {
     type Query = ();
     type Form = types::transfer::SetDownloadLimitForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/transfer/setDownloadLimit".into()
     }
     fn form(&self) -> Option<&Self::Form> {
@@ -132,9 +128,8 @@ impl Endpoint for SetDownloadLimit {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -150,7 +145,7 @@ impl Endpoint for UploadLimit {
     type Query = ();
     type Form = ();
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/transfer/uploadLimit".into()
     }
     fn method(&self) -> reqwest::Method {
@@ -158,9 +153,8 @@ impl Endpoint for UploadLimit {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -178,7 +172,7 @@ impl Endpoint for SetUploadLimit {
     type Query = ();
     type Form = types::transfer::SetUploadLimitForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/transfer/setUploadLimit".into()
     }
     fn form(&self) -> Option<&Self::Form> {
@@ -189,9 +183,8 @@ impl Endpoint for SetUploadLimit {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
@@ -209,7 +202,7 @@ impl Endpoint for BanPeers {
     type Query = ();
     type Form = types::transfer::BanPeersForm;
     type Response = String;
-    fn relative_path(&self) -> Cow<str> {
+    fn relative_path(&self) -> Cow<'_, str> {
         "/api/v2/transfer/banPeers".into()
     }
     fn form(&self) -> Option<&Self::Form> {
@@ -220,9 +213,8 @@ impl Endpoint for BanPeers {
     }
     fn check_status(&self, status: reqwest::StatusCode) -> Option<ClientError> {
         match status {
-            StatusCode::OK => None,
             StatusCode::FORBIDDEN => Some(ClientError::NeedAuthentication),
-            _ => Some(ClientError::Unknown),
+            _ => None,
         }
     }
     async fn de_response(&self, res: reqwest::Response) -> Result<Self::Response, ClientError> {
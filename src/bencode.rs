@@ -0,0 +1,156 @@
+//! A minimal bencode decoder, enough to read torrent files
+
+use crate::error::TypesError;
+use std::collections::BTreeMap;
+
+/// nesting deeper than this is rejected instead of overflowing the stack
+const MAX_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value<'a> {
+    Int(i64),
+    Bytes(&'a [u8]),
+    List(Vec<Value<'a>>),
+    /// the entries, and the dict as encoded, which info hashes are computed from
+    Dict(BTreeMap<&'a [u8], Value<'a>>, &'a [u8]),
+}
+
+impl<'a> Value<'a> {
+    pub(crate) fn get(&self, key: &str) -> Option<&Value<'a>> {
+        match self {
+            Self::Dict(entries, _) => entries.get(key.as_bytes()),
+            _ => None,
+        }
+    }
+}
+
+/// Decode a whole buffer, trailing bytes are an error
+pub(crate) fn decode(buf: &[u8]) -> Result<Value<'_>, TypesError> {
+    let mut decoder = Decoder { buf, pos: 0 };
+    let value = decoder.value(0)?;
+    if decoder.pos != buf.len() {
+        return Err(decoder.error("trailing data"));
+    }
+    Ok(value)
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+struct Decoder<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn error(&self, msg: &str) -> TypesError {
+        TypesError::Other(format!("invalid bencode at byte {}: {}", self.pos, msg))
+    }
+
+    fn peek(&self) -> Result<u8, TypesError> {
+        self.buf
+            .get(self.pos)
+            .copied()
+            .ok_or_else(|| self.error("unexpected end"))
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value<'a>, TypesError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nested too deep"));
+        }
+        match self.peek()? {
+            b'i' => {
+                self.pos += 1;
+                let n = self.until(b'e')?;
+                // no leading zero, nor negative zero
+                let digits = n.strip_prefix('-').unwrap_or(n);
+                let valid = digits == "0" && digits.len() == n.len() || !digits.starts_with('0');
+                match n.parse() {
+                    Ok(n) if valid => Ok(Value::Int(n)),
+                    _ => Err(self.error("invalid integer")),
+                }
+            }
+            b'l' => {
+                self.pos += 1;
+                let mut items = vec![];
+                while self.peek()? != b'e' {
+                    items.push(self.value(depth + 1)?);
+                }
+                self.pos += 1;
+                Ok(Value::List(items))
+            }
+            b'd' => {
+                let start = self.pos;
+                self.pos += 1;
+                let mut entries = BTreeMap::new();
+                while self.peek()? != b'e' {
+                    let key = self.bytes()?;
+                    let value = self.value(depth + 1)?;
+                    entries.insert(key, value);
+                }
+                self.pos += 1;
+                Ok(Value::Dict(entries, &self.buf[start..self.pos]))
+            }
+            b'0'..=b'9' => Ok(Value::Bytes(self.bytes()?)),
+            _ => Err(self.error("unexpected byte")),
+        }
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], TypesError> {
+        let len: usize = self
+            .until(b':')?
+            .parse()
+            .map_err(|_| self.error("invalid length"))?;
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.buf.len())
+            .ok_or_else(|| self.error("length out of bounds"))?;
+        let bytes = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    /// the ascii text up to `delimiter`, which is consumed
+    fn until(&mut self, delimiter: u8) -> Result<&'a str, TypesError> {
+        let rest = &self.buf[self.pos..];
+        let len = rest
+            .iter()
+            .position(|b| *b == delimiter)
+            .ok_or_else(|| self.error("unexpected end"))?;
+        let text = std::str::from_utf8(&rest[..len]).map_err(|_| self.error("not ascii"))?;
+        self.pos += len + 1;
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let v = decode(b"d3:cow3:moo4:spaml1:ai-3ee4:infod1:xi0eee").unwrap();
+        assert_eq!(v.get("cow"), Some(&Value::Bytes(b"moo")));
+        assert_eq!(
+            v.get("spam"),
+            Some(&Value::List(vec![Value::Bytes(b"a"), Value::Int(-3)]))
+        );
+        assert!(matches!(v.get("info"), Some(Value::Dict(_, raw)) if *raw == b"d1:xi0ee"));
+
+        for invalid in [
+            &b"i-0e"[..],
+            b"i03e",
+            b"ie",
+            b"5:abc",
+            b"l",
+            b"d3:cowe",
+            b"i1ei2e",
+        ] {
+            assert!(decode(invalid).is_err(), "{:?}", invalid);
+        }
+        let deep = [vec![b'l'; MAX_DEPTH + 2], vec![b'e'; MAX_DEPTH + 2]].concat();
+        assert!(decode(&deep).is_err());
+    }
+}
@@ -1,11 +1,25 @@
-use crate::error::ClientError;
+mod qbit_api;
+
+pub use qbit_api::QbitApi;
+
+use crate::error::{truncate_body, ClientError};
 use crate::{
     api::{self, Endpoint},
     types,
 };
-use reqwest::Client;
+use async_trait::async_trait;
+use reqwest::{header::HeaderMap, Certificate, Client, Proxy};
 use reqwest_cookie_store::{CookieStore, CookieStoreMutex};
-use std::{error::Error, io::prelude::*, path::Path, sync::Arc};
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::Mutex;
 use url::Url;
 
 #[derive(Debug, Clone)]
@@ -14,31 +28,138 @@ pub struct Credential {
     pub password: String,
 }
 
-#[derive(Debug)]
+/// What to do when a request fails with [`ClientError::NeedAuthentication`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReauthPolicy {
+    /// return the error to the caller
+    #[default]
+    Never,
+    /// login again with the stored [`Credential`] and retry the request once
+    RetryOnce,
+}
+
+/// How requests failing with a transient error are retried
+///
+/// only endpoints which are idempotent (see [`Endpoint::is_idempotent`]) are retried,
+/// unless `retry_non_idempotent` is set. the default retries `GET` endpoints up to 3 attempts
+///
+/// ```no_run
+/// use qbit_api_rs::client::{QbitClient, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let client = QbitClient::new_from_env().unwrap().with_retry_policy(RetryPolicy {
+///     max_attempts: 5,
+///     initial_backoff: Duration::from_millis(500),
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// attempts in total including the first one, `1` disables retrying
+    pub max_attempts: u32,
+    /// backoff before the first retry, doubled for every following retry
+    pub initial_backoff: Duration,
+    /// upper bound of the backoff
+    pub max_backoff: Duration,
+    /// shorten each backoff by a random fraction up to this, from `0.0` to `1.0`
+    pub jitter: f64,
+    /// also retry endpoints which are not idempotent, e.g. `torrents/add` or `torrents/delete`
+    pub retry_non_idempotent: bool,
+    /// which errors are retried, [`ClientError::is_retryable`] by default
+    pub retry_on: fn(&ClientError) -> bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            jitter: 0.5,
+            retry_non_idempotent: false,
+            retry_on: ClientError::is_retryable,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Send every request only once
+    pub fn never() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// The backoff before the `retry`th retry, starting from `1`
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+        let jitter = self.jitter.clamp(0.0, 1.0) * random_fraction();
+        backoff.mul_f64(1.0 - jitter)
+    }
+
+    fn should_retry<E>(&self, endpoint: &E, attempt: u32, error: &ClientError) -> bool
+    where
+        E: Endpoint,
+    {
+        attempt < self.max_attempts
+            && (endpoint.is_idempotent() || self.retry_non_idempotent)
+            && (self.retry_on)(error)
+    }
+}
+
+/// a random number in `[0, 1)`, good enough for jitter
+fn random_fraction() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// A clone shares the session, the cookies and the connection pool with the original
+#[derive(Debug, Clone)]
 pub struct QbitClient {
+    /// base url of the WebUI, always ends with `/`. e.g. `https://example.lan/qbit/`
     pub host: Url,
     pub auth: Credential,
     pub client: Client,
     pub cookie_store: Arc<CookieStoreMutex>,
+    pub reauth_policy: ReauthPolicy,
+    pub retry_policy: RetryPolicy,
+    /// serializes logins, so that concurrent requests with an expired SID
+    /// don't all login at the same time
+    login_lock: Arc<Mutex<()>>,
+    /// increased after every successful login
+    session_generation: Arc<AtomicU64>,
 }
 
 impl QbitClient {
     fn _try_new(host: &str, username: &str, password: &str) -> Result<Self, ClientError> {
-        let cookie_store = Arc::new(CookieStoreMutex::new(CookieStore::new(None)));
-        let client = Client::builder()
-            .cookie_provider(cookie_store.clone())
-            .build()
-            .map_err(|e| ClientError::Initialize(e.to_string()))?;
-        Ok(Self {
-            host: Url::parse(host).map_err(|e| ClientError::Initialize(e.to_string()))?,
-            auth: Credential {
-                username: username.to_owned(),
-                password: password.to_owned(),
-            },
-            client,
-            cookie_store,
-        })
+        Self::builder(host, username, password).build()
+    }
+
+    /// Create a [`QbitClientBuilder`] to configure the underlying http client
+    ///
+    /// ```no_run
+    /// use qbit_api_rs::client::QbitClient;
+    /// use std::time::Duration;
+    ///
+    /// let pem = std::fs::read("internal-ca.pem").unwrap();
+    /// let client = QbitClient::builder("https://qbit.example.lan", "admin", "adminadmin")
+    ///     .timeout(Duration::from_secs(30))
+    ///     .add_root_certificate(reqwest::Certificate::from_pem(&pem).unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder<U>(host: U, username: U, password: U) -> QbitClientBuilder
+    where
+        U: AsRef<str>,
+    {
+        QbitClientBuilder::new(host.as_ref(), username.as_ref(), password.as_ref())
     }
+
     pub fn new_with_user_pwd<U>(host: U, username: U, password: U) -> Result<Self, ClientError>
     where
         U: AsRef<str>,
@@ -58,16 +179,83 @@ impl QbitClient {
         Self::_try_new(&host, &username, &password)
     }
 
+    /// Set the [`ReauthPolicy`] of the client
+    ///
+    /// ```no_run
+    /// use qbit_api_rs::client::{QbitClient, ReauthPolicy};
+    ///
+    /// let client = QbitClient::new_from_env()
+    ///     .unwrap()
+    ///     .with_reauth_policy(ReauthPolicy::RetryOnce);
+    /// ```
+    pub fn with_reauth_policy(mut self, policy: ReauthPolicy) -> Self {
+        self.reauth_policy = policy;
+        self
+    }
+
+    /// Set the [`RetryPolicy`] of the client
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
     pub async fn _resp<E>(&self, endpoint: &E) -> Result<E::Response, ClientError>
     where
         E: Endpoint,
     {
-        let url = self.host.join(&endpoint.relative_path())?;
+        let mut attempt = 1;
+        loop {
+            match self._resp_once(endpoint).await {
+                Err(e) if self.retry_policy.should_retry(endpoint, attempt, &e) => {
+                    let backoff = self.retry_policy.backoff(attempt);
+                    log::warn!(
+                        "{} {} failed (attempt {}/{}), retry in {:?}: {}",
+                        endpoint.method(),
+                        endpoint.relative_path(),
+                        attempt,
+                        self.retry_policy.max_attempts,
+                        backoff,
+                        e
+                    );
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// send the request once, and once more after login if the [`ReauthPolicy`] allows
+    async fn _resp_once<E>(&self, endpoint: &E) -> Result<E::Response, ClientError>
+    where
+        E: Endpoint,
+    {
+        let generation = self.session_generation.load(Ordering::Acquire);
+        match self._send(endpoint).await {
+            Err(ClientError::NeedAuthentication)
+                if self.reauth_policy == ReauthPolicy::RetryOnce =>
+            {
+                log::debug!("authentication required, login again and retry");
+                self._reauth(generation).await?;
+                // the request is built from the endpoint again,
+                // so the multipart body is not consumed by the first attempt
+                self._send(endpoint).await
+            }
+            res => res,
+        }
+    }
+
+    async fn _send<E>(&self, endpoint: &E) -> Result<E::Response, ClientError>
+    where
+        E: Endpoint,
+    {
+        let url = self._url(&endpoint.relative_path())?;
         let mut request = self.client.request(endpoint.method(), url);
 
         // build Headers
         let mut headers = reqwest::header::HeaderMap::new();
         headers.insert("Referer", self.host.to_string().parse()?);
+        headers.insert("Origin", self.host.origin().ascii_serialization().parse()?);
         request = request.headers(headers);
 
         if let Some(query) = endpoint.query() {
@@ -86,16 +274,42 @@ impl QbitClient {
         log::debug!("response: {:?}", resp);
 
         // check status code, return errors that defined in api
-        if let Some(error) = endpoint.check_status(resp.status()) {
+        let status = resp.status();
+        if let Some(error) = endpoint.check_status(status) {
             return Err(error);
         }
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            return Err(ClientError::UnexpectedStatus {
+                method: endpoint.method(),
+                endpoint: endpoint.relative_path().into_owned(),
+                status,
+                body: truncate_body(&body),
+            });
+        }
         // deserialize response as string or type defined in api
         let de_resp = endpoint.de_response(resp).await?;
         Ok(de_resp)
     }
 
-    /// # `/api/v2/auth/login`
-    pub async fn auth_login(&self) -> Result<(), ClientError> {
+    /// Resolve the endpoint path against the host
+    ///
+    /// the path is resolved relative to the path of host,
+    /// so that a host like `https://example.lan/qbit/` behind a reverse proxy works
+    fn _url(&self, relative_path: &str) -> Result<Url, ClientError> {
+        Ok(self.host.join(relative_path.trim_start_matches('/'))?)
+    }
+
+    /// login again, unless someone else has already done it since `generation`
+    async fn _reauth(&self, generation: u64) -> Result<(), ClientError> {
+        let _guard = self.login_lock.lock().await;
+        if self.session_generation.load(Ordering::Acquire) != generation {
+            return Ok(());
+        }
+        self._login().await
+    }
+
+    async fn _login(&self) -> Result<(), ClientError> {
         let auth_form = types::auth::LoginForm {
             username: self.auth.username.clone(),
             password: self.auth.password.clone(),
@@ -103,1063 +317,261 @@ impl QbitClient {
         let api_auth_login = api::auth::Login { f: auth_form };
 
         {
-            let mut store = self.cookie_store.lock().unwrap();
+            let mut store = self
+                .cookie_store
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
             store.clear();
         }
 
-        let _s = self._resp(&api_auth_login).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/auth/logout`
-    pub async fn auth_logout(&self) -> Result<(), ClientError> {
-        let api_auth_logout = api::auth::Logout {};
-        let _s = self._resp(&api_auth_logout).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/app/version`
-    pub async fn app_version(&self) -> Result<String, ClientError> {
-        let api_app_version = api::app::Version {};
-        let s = self._resp(&api_app_version).await?;
-
-        Ok(s)
-    }
-
-    /// # `/api/v2/app/webapiVersion`
-    pub async fn app_webapi_version(&self) -> Result<String, ClientError> {
-        let api_app_webapi_version = api::app::WebApiVersion {};
-        let s = self._resp(&api_app_webapi_version).await?;
-
-        Ok(s)
-    }
-
-    /// # `/api/v2/app/buildInfo`
-    pub async fn app_build_info(&self) -> Result<types::app::BuildInfoResponse, ClientError> {
-        let api_build_info = api::app::BuildInfo {};
-        let de_resp = self._resp(&api_build_info).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/app/shutdown`
-    pub async fn app_shutdown(&self) -> Result<(), ClientError> {
-        let api_app_shutdown = api::app::Shutdown {};
-        let _s = self._resp(&api_app_shutdown).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/app/preferences`
-    pub async fn app_preferences(&self) -> Result<types::app::Preferences, ClientError> {
-        let api_app_preferences = api::app::Preferences {};
-        let de_resp = self._resp(&api_app_preferences).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/app/setPreferences`
-    pub async fn app_set_preferences(
-        &self,
-        f: &types::app::SetPreferencesForm,
-    ) -> Result<(), ClientError> {
-        let api_set_preferences = api::app::SetPreferences { f: f.to_owned() };
-        let _s = self._resp(&api_set_preferences).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/app/defaultSavePath`
-    pub async fn app_default_save_path(&self) -> Result<(), ClientError> {
-        let api_default_save_path = api::app::DefaultSavePath {};
-        let _s = self._resp(&api_default_save_path).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/log/main`
-    pub async fn log_main(
-        &self,
-        q: &types::log::MainQuery,
-    ) -> Result<Vec<types::log::MainResponseItem>, ClientError> {
-        let api_logmain = api::log::Main { q: q.to_owned() };
-        let de_resp = self._resp(&api_logmain).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/log/peers`
-    pub async fn log_peers(
-        &self,
-        q: &types::log::PeersQuery,
-    ) -> Result<Vec<types::log::PeersResponseItem>, ClientError> {
-        let api_logpeers = api::log::Peers { q: q.to_owned() };
-        let de_resp = self._resp(&api_logpeers).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/sync/maindata`
-    pub async fn sync_maindata(
-        &self,
-        q: &types::sync::MaindataQuery,
-    ) -> Result<types::sync::MaindataResponse, ClientError> {
-        let api_maindata = api::sync::Maindata { q: q.to_owned() };
-        let de_resp = self._resp(&api_maindata).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/sync/torrentPeers`
-    pub async fn sync_torrent_peers(
-        &self,
-        q: &types::sync::TorrentPeersQuery,
-    ) -> Result<types::sync::TorrentPeersResponse, ClientError> {
-        let api_torrent_peers = api::sync::TorrentPeers { q: q.to_owned() };
-        let de_resp = self._resp(&api_torrent_peers).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/transfer/info`
-    pub async fn transfer_info(&self) -> Result<types::transfer::InfoResponse, ClientError> {
-        let api_transfer_info = api::transfer::Info {};
-        let de_resp = self._resp(&api_transfer_info).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/transfer/speedLimitsMode`
-    pub async fn speed_limits_mode(
-        &self,
-    ) -> Result<types::transfer::SpeedLimitsModeResponse, ClientError> {
-        let api_speed_limits_mode = api::transfer::SpeedLimitsMode {};
-        let de_resp = self._resp(&api_speed_limits_mode).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/transfer/toggleSpeedLimitsMode`
-    pub async fn toggle_speed_limits_mode(&self) -> Result<(), ClientError> {
-        let api_toggle_speed_limits_mode = api::transfer::ToggleSpeedLimitsMode {};
-        let _s = self._resp(&api_toggle_speed_limits_mode).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/transfer/downloadLimit`
-    pub async fn download_limit(&self) -> Result<u64, ClientError> {
-        let api_download_limit = api::transfer::DownloadLimit {};
-        let s = self._resp(&api_download_limit).await?;
-        let dl_speed: u64 = s.parse().map_err(|_e| ClientError::ParseError)?;
-        Ok(dl_speed)
-    }
-
-    /// # `/api/v2/transfer/setDownloadLimit`
-    pub async fn set_download_limit(&self, limit: u64) -> Result<(), ClientError> {
-        let api_set_download_limit = api::transfer::SetDownloadLimit {
-            f: types::transfer::SetDownloadLimitForm { limit },
-        };
-        let _s = self._resp(&api_set_download_limit).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/transfer/uploadLimit`
-    pub async fn upload_limit(&self) -> Result<u64, ClientError> {
-        let api_upload_limit = api::transfer::UploadLimit {};
-        let s = self._resp(&api_upload_limit).await?;
-        let ul_speed: u64 = s.parse().map_err(|_e| ClientError::ParseError)?;
-        Ok(ul_speed)
-    }
-
-    /// # `/api/v2/transfer/setUploadLimit`
-    pub async fn set_upload_limit(&self, limit: u64) -> Result<(), ClientError> {
-        let api_set_upload_limit = api::transfer::SetUploadLimit {
-            f: types::transfer::SetUploadLimitForm { limit },
-        };
-        let _s = self._resp(&api_set_upload_limit).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/transfer/banPeers`
-    pub async fn ban_peers<T>(&self, peers: &[T]) -> Result<(), ClientError>
-    where
-        T: AsRef<str>,
-    {
-        let peers: Vec<String> = peers.iter().map(|p| p.as_ref().to_owned()).collect();
-
-        let f = types::transfer::BanPeersForm { peers };
-        let api_ban_peers = api::transfer::BanPeers { f };
-        let _s = self._resp(&api_ban_peers).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/info`
-    pub async fn torrents_info(
-        &self,
-        q: &types::torrents::InfoQuery,
-    ) -> Result<types::torrents::InfoResponse, ClientError> {
-        let api_torrents_info = api::torrents::Info { q: q.to_owned() };
-        let de_resp = self._resp(&api_torrents_info).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/torrents/properties`
-    pub async fn torrents_properties(
-        &self,
-        hash: &str,
-    ) -> Result<types::torrents::PropertiesResponse, ClientError> {
-        let q = types::torrents::PropertiesQuery {
-            hash: hash.to_owned(),
-        };
-        let api_torrents_properties = api::torrents::Properties { q };
-        let de_resp = self._resp(&api_torrents_properties).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/torrents/trackers`
-    pub async fn torrents_trackers(
-        &self,
-        hash: &str,
-    ) -> Result<types::torrents::TrackersResponse, ClientError> {
-        let q = types::torrents::TrackersQuery {
-            hash: hash.to_owned(),
-        };
-        let api_torrents_trackers = api::torrents::Trackers { q };
-        let de_resp = self._resp(&api_torrents_trackers).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/torrents/webseeds`
-    pub async fn torrents_webseeds(
-        &self,
-        hash: &str,
-    ) -> Result<types::torrents::WebseedsResponse, ClientError> {
-        let q = types::torrents::WebseedsQuery {
-            hash: hash.to_owned(),
-        };
-        let api_torrents_webseeds = api::torrents::Webseeds { q };
-        let de_resp = self._resp(&api_torrents_webseeds).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/torrents/files`
-    pub async fn torrents_files(
-        &self,
-        hash: &str,
-        indexes: Option<&[u64]>,
-    ) -> Result<types::torrents::FilesResponse, ClientError> {
-        let indexes = indexes.map(|v| {
-            v.iter()
-                .map(|i| i.to_string())
-                .collect::<Vec<String>>()
-                .join("|")
-        });
-
-        let q = types::torrents::FilesQuery {
-            hash: hash.to_owned(),
-            indexes,
-        };
-        let api_torrents_files = api::torrents::Files { q };
-        let de_resp = self._resp(&api_torrents_files).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/torrents/pieceStates`
-    pub async fn torrents_piece_states(
-        &self,
-        hash: &str,
-    ) -> Result<types::torrents::PieceStatesResponse, ClientError> {
-        let q = types::torrents::PieceStatesQuery {
-            hash: hash.to_owned(),
-        };
-        let api_torrents_piece_states = api::torrents::PieceStates { q };
-        let de_resp = self._resp(&api_torrents_piece_states).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/torrents/pieceHashes`
-    pub async fn torrents_piece_hashes(
-        &self,
-        hash: &str,
-    ) -> Result<types::torrents::PieceHashesResponse, ClientError> {
-        let q = types::torrents::PieceHashesQuery {
-            hash: hash.to_owned(),
-        };
-        let api_torrents_piece_hashes = api::torrents::PieceHashes { q };
-        let de_resp = self._resp(&api_torrents_piece_hashes).await?;
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/torrents/pause`
-    pub async fn torrents_pause<T>(&self, hashes: &[T]) -> Result<(), ClientError>
-    where
-        T: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::PauseForm { hashes };
-        let api_torrents_pause = api::torrents::Pause { f };
-        let _s = self._resp(&api_torrents_pause).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/resume`
-    pub async fn torrents_resume<T>(&self, hashes: &[T]) -> Result<(), ClientError>
-    where
-        T: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::ResumeForm { hashes };
-        let api_torrents_resume = api::torrents::Resume { f };
-        let _s = self._resp(&api_torrents_resume).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/delete`
-    pub async fn torrents_delete<T>(
-        &self,
-        hashes: &[T],
-        delete_files: bool,
-    ) -> Result<(), ClientError>
-    where
-        T: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::DeleteForm {
-            hashes,
-            delete_files,
-        };
-        let api_torrents_delete = api::torrents::Delete { f };
-        let _s = self._resp(&api_torrents_delete).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/recheck`
-    pub async fn torrents_recheck<H>(&self, hashes: &[H]) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::RecheckForm { hashes };
-        let api_torrents_recheck = api::torrents::Recheck { f };
-        let _s = self._resp(&api_torrents_recheck).await?;
+        let _s = self._send(&api_auth_login).await?;
+        self.session_generation.fetch_add(1, Ordering::AcqRel);
 
         Ok(())
     }
+}
 
-    /// # `/api/v2/torrents/reannounce`
-    pub async fn torrents_reannounce<H>(&self, hashes: &[H]) -> Result<(), ClientError>
+#[async_trait]
+impl QbitApi for QbitClient {
+    async fn execute<E>(&self, endpoint: &E) -> Result<E::Response, ClientError>
     where
-        H: AsRef<str>,
+        E: Endpoint + Sync,
     {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::ReannounceForm { hashes };
-        let api_torrents_reannounce = api::torrents::Reannounce { f };
-        let _s = self._resp(&api_torrents_reannounce).await?;
-
-        Ok(())
+        self._resp(endpoint).await
     }
 
-    /// # `/api/v2/torrents/add`
-    pub async fn torrents_add_by_url<U>(&self, urls: &[U]) -> Result<(), ClientError>
-    where
-        U: AsRef<str>,
-    {
-        let urls: Vec<String> = urls.iter().map(|u| u.as_ref().to_owned()).collect();
-        let ta = types::torrents::AddMultipart {
-            urls,
-            torrents: vec![],
-            ..Default::default()
-        };
-        self.torrents_add(ta).await?;
-
-        Ok(())
+    /// # `/api/v2/auth/login`
+    async fn auth_login(&self) -> Result<(), ClientError> {
+        let _guard = self.login_lock.lock().await;
+        self._login().await
     }
+}
 
-    /// # `/api/v2/torrents/add`
-    pub async fn torrents_add_by_file<F>(&self, files: &[F]) -> Result<(), ClientError>
-    where
-        F: AsRef<Path>,
-    {
-        type VecOfNameAndContent = Vec<(String, Vec<u8>)>;
-        let fc = |x: &F| -> Result<(String, Vec<u8>), Box<dyn Error>> {
-            let mut f = std::fs::File::open(x.as_ref())?;
-            let mut buffer = Vec::new();
-            f.read_to_end(&mut buffer)?;
-            Ok((
-                x.as_ref()
-                    .file_name()
-                    .ok_or("no file name")?
-                    .to_string_lossy()
-                    .to_string(),
-                buffer,
-            ))
-        };
-        let files: Result<VecOfNameAndContent, Box<dyn Error>> = files.iter().map(fc).collect();
-        let files = files.map_err(|_| ClientError::Other("".into()))?;
-        let ta = types::torrents::AddMultipart {
-            urls: vec![],
-            torrents: files,
-            ..Default::default()
-        };
-        self.torrents_add(ta).await?;
-
-        Ok(())
-    }
+/// Builder of [`QbitClient`], created by [`QbitClient::builder`]
+#[derive(Debug)]
+pub struct QbitClientBuilder {
+    host: String,
+    auth: Credential,
+    reauth_policy: ReauthPolicy,
+    retry_policy: RetryPolicy,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    default_headers: HeaderMap,
+    accept_invalid_certs: bool,
+    root_certificates: Vec<Certificate>,
+    proxy: Option<Proxy>,
+    client: Option<(Client, Arc<CookieStoreMutex>)>,
+}
 
-    /// # `/api/v2/torrents/add`
-    async fn torrents_add(&self, ta: types::torrents::AddMultipart) -> Result<(), ClientError> {
-        let api_torrents_add = api::torrents::Add { mp: ta };
-        if api_torrents_add.multipart().is_none() {
-            return Err(ClientError::InvalidMultipart("no valid multipart".into()));
+impl QbitClientBuilder {
+    fn new(host: &str, username: &str, password: &str) -> Self {
+        Self {
+            host: host.to_owned(),
+            auth: Credential {
+                username: username.to_owned(),
+                password: password.to_owned(),
+            },
+            reauth_policy: ReauthPolicy::default(),
+            retry_policy: RetryPolicy::default(),
+            timeout: None,
+            connect_timeout: None,
+            user_agent: None,
+            default_headers: HeaderMap::new(),
+            accept_invalid_certs: false,
+            root_certificates: vec![],
+            proxy: None,
+            client: None,
         }
-        let _s = self._resp(&api_torrents_add).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/addTrackers`
-    pub async fn torrents_add_trackers<H, U>(&self, hash: H, urls: &[U]) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-        U: AsRef<str>,
-    {
-        let urls: Vec<String> = urls.iter().map(|u| u.as_ref().to_owned()).collect();
-
-        let f = types::torrents::AddTrackersForm {
-            hash: hash.as_ref().to_owned(),
-            urls,
-        };
-        let api_torrents_add_trackers = api::torrents::AddTrackers { f };
-        let _s = self._resp(&api_torrents_add_trackers).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/editTracker`
-    pub async fn torrents_edit_tracker<H, U>(
-        &self,
-        hash: H,
-        orig_url: U,
-        new_url: U,
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-        U: AsRef<str>,
-    {
-        let f = types::torrents::EditTrackerForm {
-            hash: hash.as_ref().to_owned(),
-            orig_url: orig_url.as_ref().to_owned(),
-            new_url: new_url.as_ref().to_owned(),
-        };
-        let api_torrents_edit_tracker = api::torrents::EditTracker { f };
-        let _s = self._resp(&api_torrents_edit_tracker).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/removeTrackers`
-    pub async fn torrents_remove_trackers<H, U>(
-        &self,
-        hash: H,
-        urls: &[U],
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-        U: AsRef<str>,
-    {
-        let urls: Vec<String> = urls.iter().map(|u| u.as_ref().to_owned()).collect();
-
-        let f = types::torrents::RemoveTrackersForm {
-            hash: hash.as_ref().to_owned(),
-            urls,
-        };
-        let api_torrents_remove_trackers = api::torrents::RemoveTrackers { f };
-        let _s = self._resp(&api_torrents_remove_trackers).await?;
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/addPeers`
-    pub async fn torrents_add_peers<H, P>(
-        &self,
-        hashes: &[H],
-        peers: &[P],
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-        P: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-        let peers: Vec<String> = peers.iter().map(|p| p.as_ref().to_owned()).collect();
-
-        let f = types::torrents::AddPeersForm { hashes, peers };
-        let api_torrents_add_peers = api::torrents::AddPeers { f };
-        let _s = self._resp(&api_torrents_add_peers).await.unwrap();
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/increasePrio`
-    pub async fn torrents_increase_prio<H>(&self, hashes: &[H]) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::IncreasePrioForm { hashes };
-        let api_torrents_increase_prio = api::torrents::IncreasePrio { f };
-        let _s = self._resp(&api_torrents_increase_prio).await.unwrap();
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/decreasePrio`
-    pub async fn torrents_decrease_prio<H>(&self, hashes: &[H]) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::DecreasePrioForm { hashes };
-        let api_torrents_decrease_prio = api::torrents::DecreasePrio { f };
-        let _s = self._resp(&api_torrents_decrease_prio).await.unwrap();
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/topPrio`
-    pub async fn torrents_top_prio<H>(&self, hashes: &[H]) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::TopPrioForm { hashes };
-        let api_torrents_top_prio = api::torrents::TopPrio { f };
-        let _s = self._resp(&api_torrents_top_prio).await.unwrap();
-
-        Ok(())
     }
 
-    /// # `/api/v2/torrents/bottomPrio`
-    pub async fn torrents_bottom_prio<H>(&self, hashes: &[H]) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::BottomPrioForm { hashes };
-        let api_torrents_bottom_prio = api::torrents::BottomPrio { f };
-        let _s = self._resp(&api_torrents_bottom_prio).await.unwrap();
-
-        Ok(())
+    pub fn reauth_policy(mut self, policy: ReauthPolicy) -> Self {
+        self.reauth_policy = policy;
+        self
     }
 
-    /// # `/api/v2/torrents/filePrio`
-    // TODO: Implement
-
-    /// # `/api/v2/torrents/downloadLimit`
-    pub async fn torrents_download_limit<H>(
-        &self,
-        hashes: &[H],
-    ) -> Result<types::torrents::DownloadLimitResponse, ClientError>
-    where
-        H: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::DownloadLimitForm { hashes };
-        let api_torrents_download_limit = api::torrents::DownloadLimit { f };
-        let de_resp = self._resp(&api_torrents_download_limit).await.unwrap();
-
-        Ok(de_resp)
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
     }
 
-    /// # `/api/v2/torrents/setDownloadLimit`
-    pub async fn torrents_set_download_limit<H>(
-        &self,
-        hashes: &[H],
-        limit: u64,
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::SetDownloadLimitForm { hashes, limit };
-        let api_torrents_set_download_limit = api::torrents::SetDownloadLimit { f };
-        let _s = self._resp(&api_torrents_set_download_limit).await.unwrap();
-
-        Ok(())
+    /// timeout of the whole request, from connecting to reading the body
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
     }
 
-    /// # `/api/v2/torrents/setShareLimits`
-    pub async fn torrents_set_share_limits<H>(
-        &self,
-        hashes: &[H],
-        ratio_limit: types::torrents::RatioLimit,
-        seeding_time_limit: i64,
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::SetShareLimitsForm {
-            hashes,
-            ratio_limit,
-            seeding_time_limit,
-        };
-        let api_torrents_set_share_limits = api::torrents::SetShareLimits { f };
-        let _s = self._resp(&api_torrents_set_share_limits).await.unwrap();
-
-        Ok(())
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
     }
 
-    /// # `/api/v2/torrents/uploadLimit`
-    pub async fn torrents_upload_limit<H>(
-        &self,
-        hashes: &[H],
-    ) -> Result<types::torrents::UploadLimitResponse, ClientError>
+    pub fn user_agent<U>(mut self, user_agent: U) -> Self
     where
-        H: AsRef<str>,
+        U: AsRef<str>,
     {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::UploadLimitForm { hashes };
-        let api_torrents_upload_limit = api::torrents::UploadLimit { f };
-        let de_resp = self._resp(&api_torrents_upload_limit).await.unwrap();
-
-        Ok(de_resp)
+        self.user_agent = Some(user_agent.as_ref().to_owned());
+        self
     }
 
-    /// # `/api/v2/torrents/setUploadLimit`
-    pub async fn torrents_set_upload_limit<H>(
-        &self,
-        hashes: &[H],
-        limit: u64,
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::SetUploadLimitForm { hashes, limit };
-        let api_torrents_set_upload_limit = api::torrents::SetUploadLimit { f };
-        let _s = self._resp(&api_torrents_set_upload_limit).await.unwrap();
-
-        Ok(())
+    /// headers sent with every request, e.g. the auth header of a reverse proxy
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+        self
     }
 
-    /// # `/api/v2/torrents/setLocation`
-    pub async fn torrents_set_location<H, L>(
-        &self,
-        hashes: &[H],
-        location: L,
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-        L: AsRef<Path>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::SetLocationForm {
-            hashes,
-            location: location.as_ref().to_string_lossy().to_string(),
-        };
-        let api_torrents_set_location = api::torrents::SetLocation { f };
-        let _s = self._resp(&api_torrents_set_location).await.unwrap();
-
-        Ok(())
+    /// `Warning`: accept any certificate, including self-signed and expired ones
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
     }
 
-    /// # `/api/v2/torrents/rename`
-    pub async fn torernts_rename<H, N>(&self, hash: H, name: N) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-        N: AsRef<str>,
-    {
-        let f = types::torrents::RenameForm {
-            hash: hash.as_ref().to_owned(),
-            name: name.as_ref().to_owned(),
-        };
-        let api_torrents_rename = api::torrents::Rename { f };
-        let _s = self._resp(&api_torrents_rename).await.unwrap();
-
-        Ok(())
+    /// trust an additional root CA, e.g. the internal CA of a reverse proxy
+    pub fn add_root_certificate(mut self, cert: Certificate) -> Self {
+        self.root_certificates.push(cert);
+        self
     }
 
-    /// # `/api/v2/torrents/setCategory`
-    pub async fn torernts_set_category<H, C>(
-        &self,
-        hashes: &[H],
-        category: C,
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-        C: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::SetCategoryForm {
-            hashes,
-            category: category.as_ref().to_owned(),
-        };
-        let api_torrents_set_category = api::torrents::SetCategory { f };
-        let _s = self._resp(&api_torrents_set_category).await.unwrap();
-
-        Ok(())
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
     }
 
-    /// # `/api/v2/torrents/categories`
-    pub async fn torrents_categories(
-        &self,
-    ) -> Result<types::torrents::CategoriesResponse, ClientError> {
-        let api_torrents_categories = api::torrents::Categories {};
-        let de_resp = self._resp(&api_torrents_categories).await.unwrap();
-
-        Ok(de_resp)
+    /// Use a pre-built `reqwest::Client`
+    ///
+    /// `cookie_store` must be the cookie provider of `client`, otherwise the SID cookie
+    /// can't be kept. The http options of this builder are ignored.
+    pub fn reqwest_client(mut self, client: Client, cookie_store: Arc<CookieStoreMutex>) -> Self {
+        self.client = Some((client, cookie_store));
+        self
     }
 
-    /// # `/api/v2/torrents/createCategory`
-    pub async fn torrents_create_category<C, P>(
-        &self,
-        category: C,
-        save_path: P,
-    ) -> Result<(), ClientError>
-    where
-        C: AsRef<str>,
-        P: AsRef<Path>,
-    {
-        let f = types::torrents::CreateCategoryForm {
-            category: category.as_ref().to_owned(),
-            save_path: save_path.as_ref().to_string_lossy().to_string(),
-        };
-        let api_torrents_create_category = api::torrents::CreateCategory { f };
-        let _s = self._resp(&api_torrents_create_category).await.unwrap();
-
-        Ok(())
-    }
+    pub fn build(self) -> Result<QbitClient, ClientError> {
+        let mut host =
+            Url::parse(&self.host).map_err(|e| ClientError::Initialize(e.to_string()))?;
+        // a base path without trailing `/` would be replaced when joining the endpoint path
+        if !host.path().ends_with('/') {
+            let path = format!("{}/", host.path());
+            host.set_path(&path);
+        }
 
-    /// # `/api/v2/torrents/editCategory`
-    pub async fn torrents_edit_category<C, P>(
-        &self,
-        category: C,
-        save_path: P,
-    ) -> Result<(), ClientError>
-    where
-        C: AsRef<str>,
-        P: AsRef<Path>,
-    {
-        let f = types::torrents::EditCategoryForm {
-            category: category.as_ref().to_owned(),
-            save_path: save_path.as_ref().to_string_lossy().to_string(),
+        let (client, cookie_store) = match self.client {
+            Some(client) => client,
+            None => {
+                let cookie_store = Arc::new(CookieStoreMutex::new(CookieStore::new(None)));
+                let mut builder = Client::builder()
+                    .cookie_provider(cookie_store.clone())
+                    .default_headers(self.default_headers)
+                    .danger_accept_invalid_certs(self.accept_invalid_certs);
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                for cert in self.root_certificates {
+                    builder = builder.add_root_certificate(cert);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                let client = builder
+                    .build()
+                    .map_err(|e| ClientError::Initialize(e.to_string()))?;
+                (client, cookie_store)
+            }
         };
-        let api_torrents_edit_category = api::torrents::EditCategory { f };
-        let _s = self._resp(&api_torrents_edit_category).await.unwrap();
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/removeCategories`
-    pub async fn torrents_remove_categories<C>(&self, categories: &[C]) -> Result<(), ClientError>
-    where
-        C: AsRef<str>,
-    {
-        let categories: Vec<String> = categories.iter().map(|c| c.as_ref().to_owned()).collect();
-
-        let f = types::torrents::RemoveCategoriesForm { categories };
-        let api_torrents_remove_categories = api::torrents::RemoveCategories { f };
-        let _s = self._resp(&api_torrents_remove_categories).await.unwrap();
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/addTags`
-    pub async fn torrents_add_tags<H, T>(&self, hashes: &[H], tags: &[T]) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-        T: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-        let tags: Vec<String> = tags.iter().map(|t| t.as_ref().to_owned()).collect();
-
-        let f = types::torrents::AddTagsForm { hashes, tags };
-        let api_torrents_add_tags = api::torrents::AddTags { f };
-        let _s = self._resp(&api_torrents_add_tags).await.unwrap();
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/removeTags`
-    pub async fn torrents_remove_tags<H, T>(
-        &self,
-        hashes: &[H],
-        tags: &[T],
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-        T: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-        let tags: Vec<String> = tags.iter().map(|t| t.as_ref().to_owned()).collect();
-
-        let f = types::torrents::RemoveTagsForm { hashes, tags };
-        let api_torrents_remove_tags = api::torrents::RemoveTags { f };
-        let _s = self._resp(&api_torrents_remove_tags).await.unwrap();
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/tags`
-    pub async fn torrents_tags(&self) -> Result<types::torrents::TagsResponse, ClientError> {
-        let api_torrents_tags = api::torrents::Tags {};
-        let de_resp = self._resp(&api_torrents_tags).await.unwrap();
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/torrents/createTags`
-    pub async fn torrens_create_tags<T>(&self, tags: &[T]) -> Result<(), ClientError>
-    where
-        T: AsRef<str>,
-    {
-        let tags: Vec<String> = tags.iter().map(|t| t.as_ref().to_owned()).collect();
-
-        let f = types::torrents::CreateTagsForm { tags };
-        let api_torrents_create_tags = api::torrents::CreateTags { f };
-        let _s = self._resp(&api_torrents_create_tags).await.unwrap();
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/deleteTags`
-    pub async fn torrents_delete_tags<T>(&self, tags: &[T]) -> Result<(), ClientError>
-    where
-        T: AsRef<str>,
-    {
-        let tags: Vec<String> = tags.iter().map(|t| t.as_ref().to_owned()).collect();
-
-        let f = types::torrents::DeleteTagsForm { tags };
-        let api_torrents_delete_tags = api::torrents::DeleteTags { f };
-        let _s = self._resp(&api_torrents_delete_tags).await.unwrap();
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/setAutoManagement`
-    pub async fn torrents_set_auto_management<H>(
-        &self,
-        hashes: &[H],
-        enable: bool,
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::SetAutoManagementForm { hashes, enable };
-        let api_torrents_set_automanagement = api::torrents::SetAutoManagement { f };
-        let _s = self._resp(&api_torrents_set_automanagement).await.unwrap();
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/toggleSequentialDownload`
-    pub async fn torrents_toggle_sequential_download<H>(
-        &self,
-        hashes: &[H],
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::ToggleSequentialDownloadForm { hashes };
-        let api_torrents_toggle_sequential_download = api::torrents::ToggleSequentialDownload { f };
-        let _s = self
-            ._resp(&api_torrents_toggle_sequential_download)
-            .await
-            .unwrap();
 
-        Ok(())
+        Ok(QbitClient {
+            host,
+            auth: self.auth,
+            client,
+            cookie_store,
+            reauth_policy: self.reauth_policy,
+            retry_policy: self.retry_policy,
+            login_lock: Arc::new(Mutex::new(())),
+            session_generation: Arc::new(AtomicU64::new(0)),
+        })
     }
+}
 
-    /// # `/api/v2/torrents/toggleFirstLastPiecePrio`
-    pub async fn torrents_toggle_first_last_piece_prio<H>(
-        &self,
-        hashes: &[H],
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::ToggleFirstLastPiecePrioForm { hashes };
-        let api_torrents_toggle_first_last_piece_prio =
-            api::torrents::ToggleFirstLastPiecePrio { f };
-        let _s = self
-            ._resp(&api_torrents_toggle_first_last_piece_prio)
-            .await
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Forwarded-User", "admin".parse().unwrap());
+        let client = QbitClient::builder("https://qbit.example.lan", "admin", "adminadmin")
+            .reauth_policy(ReauthPolicy::RetryOnce)
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(5))
+            .user_agent("qbit-api-rs")
+            .default_headers(headers)
+            .danger_accept_invalid_certs(true)
+            .proxy(Proxy::all("http://127.0.0.1:3128").unwrap())
+            .build()
             .unwrap();
+        assert_eq!(client.host.as_str(), "https://qbit.example.lan/");
+        assert_eq!(client.reauth_policy, ReauthPolicy::RetryOnce);
 
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/setForceStart`
-    pub async fn torrents_set_force_start<H>(
-        &self,
-        hashes: &[H],
-        value: bool,
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::SetForceStartForm { hashes, value };
-        let api_torrents_set_force_start = api::torrents::SetForceStart { f };
-        let _s = self._resp(&api_torrents_set_force_start).await.unwrap();
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/setSuperSeeding`
-    pub async fn torrents_set_super_seeding<H>(
-        &self,
-        hashes: &[H],
-        value: bool,
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-    {
-        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
-
-        let f = types::torrents::SetSuperSeedingForm { hashes, value };
-        let api_torrents_set_super_seeding = api::torrents::SetSuperSeeding { f };
-        let _s = self._resp(&api_torrents_set_super_seeding).await.unwrap();
-
-        Ok(())
-    }
-
-    /// # `/api/v2/torrents/renameFile`
-    pub async fn torrents_rename_file<H, P>(
-        &self,
-        hash: H,
-        old_path: P,
-        new_path: P,
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-        P: AsRef<Path>,
-    {
-        let f = types::torrents::RenameFileForm {
-            hash: hash.as_ref().to_owned(),
-            old_path: old_path.as_ref().to_string_lossy().to_string(),
-            new_path: new_path.as_ref().to_string_lossy().to_string(),
-        };
-        let api_torrents_rename_file = api::torrents::RenameFile { f };
-        let _s = self._resp(&api_torrents_rename_file).await.unwrap();
-
-        Ok(())
+        let err = QbitClient::builder("not a url", "admin", "adminadmin")
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, ClientError::Initialize(_)));
     }
 
-    /// # `/api/v2/torrents/renameFolder`
-    pub async fn torrents_rename_folder<H, P>(
-        &self,
-        hash: H,
-        old_path: P,
-        new_path: P,
-    ) -> Result<(), ClientError>
-    where
-        H: AsRef<str>,
-        P: AsRef<Path>,
-    {
-        let f = types::torrents::RenameFolderForm {
-            hash: hash.as_ref().to_owned(),
-            old_path: old_path.as_ref().to_string_lossy().to_string(),
-            new_path: new_path.as_ref().to_string_lossy().to_string(),
+    #[test]
+    fn test_retry_backoff() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(350),
+            jitter: 0.0,
+            ..Default::default()
         };
-        let api_torrents_rename_folder = api::torrents::RenameFolder { f };
-        let _s = self._resp(&api_torrents_rename_folder).await.unwrap();
-
-        Ok(())
-    }
-
-    /// # `/api/v2/search/start`
-    pub async fn search_start<T>(
-        &self,
-        pattern: T,
-        plugins: T,
-        category: T,
-    ) -> Result<types::search::StartResponse, ClientError>
-    where
-        T: AsRef<str>,
-    {
-        let f = types::search::StartForm {
-            pattern: pattern.as_ref().to_owned(),
-            plugins: plugins.as_ref().to_owned(),
-            category: category.as_ref().to_owned(),
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff(100), Duration::from_millis(350));
+
+        let policy = RetryPolicy {
+            jitter: 0.5,
+            ..policy
         };
-        let api_search_start = api::search::Start { f };
-        let de_resp = self._resp(&api_search_start).await.unwrap();
-
-        Ok(de_resp)
-    }
-
-    /// # `/api/v2/search/stop`
-    pub async fn search_stop(&self, id: u64) -> Result<(), ClientError> {
-        let f = types::search::StopForm { id };
-        let api_search_stop = api::search::Stop { f };
-        let _s = self._resp(&api_search_stop).await.unwrap();
-
-        Ok(())
-    }
-
-    /// # `/api/v2/search/status`
-    pub async fn search_status(
-        &self,
-        id: Option<u64>,
-    ) -> Result<types::search::StatusResponse, ClientError> {
-        let q = types::search::StatusQuery { id };
-        let api_search_status = api::search::Status { q };
-        let de_resp = self._resp(&api_search_status).await.unwrap();
-
-        Ok(de_resp)
+        for _ in 0..100 {
+            let backoff = policy.backoff(2);
+            assert!(backoff > Duration::from_millis(100) && backoff <= Duration::from_millis(200));
+        }
     }
 
-    /// # `/api/v2/search/results`
-    pub async fn search_results(
-        &self,
-        id: u64,
-        limit: Option<i64>,
-        offset: Option<i64>,
-    ) -> Result<types::search::ResultsResponse, ClientError> {
-        let q = types::search::ResultsQuery { id, limit, offset };
-        let api_search_results = api::search::Results { q };
-        let de_resp = self._resp(&api_search_results).await.unwrap();
+    #[test]
+    fn test_url_with_base_path() {
+        let cases = [
+            ("http://127.0.0.1:8080", "http://127.0.0.1:8080/"),
+            ("http://127.0.0.1:8080/", "http://127.0.0.1:8080/"),
+            ("https://example.lan/qbit", "https://example.lan/qbit/"),
+            ("https://example.lan/qbit/", "https://example.lan/qbit/"),
+            ("https://example.lan/a/b", "https://example.lan/a/b/"),
+        ];
+        for (host, base) in cases {
+            let client = QbitClient::new_with_user_pwd(host, "admin", "adminadmin").unwrap();
+            assert_eq!(client.host.as_str(), base);
+            assert_eq!(
+                client._url("/api/v2/torrents/info").unwrap().as_str(),
+                format!("{}api/v2/torrents/info", base)
+            );
+        }
 
-        Ok(de_resp)
+        let client =
+            QbitClient::new_with_user_pwd("https://example.lan:8443/qbit/", "admin", "adminadmin")
+                .unwrap();
+        assert_eq!(
+            client.host.origin().ascii_serialization(),
+            "https://example.lan:8443"
+        );
     }
 }
@@ -0,0 +1,1812 @@
+use crate::api::{self, Endpoint};
+use crate::error::{truncate_body, ClientError};
+use crate::magnet::MagnetLink;
+use crate::metainfo;
+use crate::search::{self, SearchJob};
+use crate::selector::{TorrentSelector, TorrentTargets};
+use crate::types;
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::future::{join_all, try_join_all};
+use futures_util::stream::{self, BoxStream, StreamExt};
+use std::collections::{HashSet, VecDeque};
+use std::io;
+use std::path::Path;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// The operations of the qBittorrent Web API
+///
+/// an implementor only provides [`QbitApi::execute`] and [`QbitApi::auth_login`],
+/// every other operation is built on top of `execute`.
+/// so a fake in unit tests, or a middleware wrapping another `QbitApi`
+/// (metrics, caching, rate limiting...), gets all of them for free
+///
+/// ```no_run
+/// use qbit_api_rs::client::{QbitApi, QbitClient};
+///
+/// async fn names<A: QbitApi>(api: &A) -> Vec<String> {
+///     let torrents = api.torrents_info(&Default::default()).await.unwrap_or_default();
+///     torrents.into_iter().map(|t| t.name).collect()
+/// }
+/// ```
+#[async_trait]
+pub trait QbitApi: Send + Sync {
+    /// Send the request described by `endpoint` and deserialize its response
+    async fn execute<E>(&self, endpoint: &E) -> Result<E::Response, ClientError>
+    where
+        E: Endpoint + Sync;
+
+    /// # `/api/v2/auth/login`
+    async fn auth_login(&self) -> Result<(), ClientError>;
+
+    /// # `/api/v2/auth/logout`
+    async fn auth_logout(&self) -> Result<(), ClientError> {
+        let api_auth_logout = api::auth::Logout {};
+        let _s = self.execute(&api_auth_logout).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/app/version`
+    async fn app_version(&self) -> Result<String, ClientError> {
+        let api_app_version = api::app::Version {};
+        let s = self.execute(&api_app_version).await?;
+
+        Ok(s)
+    }
+
+    /// # `/api/v2/app/webapiVersion`
+    async fn app_webapi_version(&self) -> Result<String, ClientError> {
+        let api_app_webapi_version = api::app::WebApiVersion {};
+        let s = self.execute(&api_app_webapi_version).await?;
+
+        Ok(s)
+    }
+
+    /// # `/api/v2/app/buildInfo`
+    async fn app_build_info(&self) -> Result<types::app::BuildInfoResponse, ClientError> {
+        let api_build_info = api::app::BuildInfo {};
+        let de_resp = self.execute(&api_build_info).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/app/shutdown`
+    async fn app_shutdown(&self) -> Result<(), ClientError> {
+        let api_app_shutdown = api::app::Shutdown {};
+        let _s = self.execute(&api_app_shutdown).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/app/preferences`
+    async fn app_preferences(&self) -> Result<types::app::Preferences, ClientError> {
+        let api_app_preferences = api::app::Preferences {};
+        let de_resp = self.execute(&api_app_preferences).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/app/setPreferences`
+    async fn app_set_preferences(
+        &self,
+        f: &types::app::SetPreferencesForm,
+    ) -> Result<(), ClientError> {
+        let api_set_preferences = api::app::SetPreferences { f: f.to_owned() };
+        let _s = self.execute(&api_set_preferences).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/app/defaultSavePath`
+    async fn app_default_save_path(&self) -> Result<(), ClientError> {
+        let api_default_save_path = api::app::DefaultSavePath {};
+        let _s = self.execute(&api_default_save_path).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/log/main`
+    async fn log_main(
+        &self,
+        q: &types::log::MainQuery,
+    ) -> Result<Vec<types::log::MainResponseItem>, ClientError> {
+        let api_logmain = api::log::Main { q: q.to_owned() };
+        let de_resp = self.execute(&api_logmain).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/log/peers`
+    async fn log_peers(
+        &self,
+        q: &types::log::PeersQuery,
+    ) -> Result<Vec<types::log::PeersResponseItem>, ClientError> {
+        let api_logpeers = api::log::Peers { q: q.to_owned() };
+        let de_resp = self.execute(&api_logpeers).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/sync/maindata`
+    async fn sync_maindata(
+        &self,
+        q: &types::sync::MaindataQuery,
+    ) -> Result<types::sync::MaindataResponse, ClientError> {
+        let api_maindata = api::sync::Maindata { q: q.to_owned() };
+        let de_resp = self.execute(&api_maindata).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/sync/torrentPeers`
+    async fn sync_torrent_peers(
+        &self,
+        q: &types::sync::TorrentPeersQuery,
+    ) -> Result<types::sync::TorrentPeersResponse, ClientError> {
+        let api_torrent_peers = api::sync::TorrentPeers { q: q.to_owned() };
+        let de_resp = self.execute(&api_torrent_peers).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/transfer/info`
+    async fn transfer_info(&self) -> Result<types::transfer::InfoResponse, ClientError> {
+        let api_transfer_info = api::transfer::Info {};
+        let de_resp = self.execute(&api_transfer_info).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/transfer/speedLimitsMode`
+    async fn speed_limits_mode(
+        &self,
+    ) -> Result<types::transfer::SpeedLimitsModeResponse, ClientError> {
+        let api_speed_limits_mode = api::transfer::SpeedLimitsMode {};
+        let de_resp = self.execute(&api_speed_limits_mode).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/transfer/toggleSpeedLimitsMode`
+    async fn toggle_speed_limits_mode(&self) -> Result<(), ClientError> {
+        let api_toggle_speed_limits_mode = api::transfer::ToggleSpeedLimitsMode {};
+        let _s = self.execute(&api_toggle_speed_limits_mode).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/transfer/downloadLimit`
+    async fn download_limit(&self) -> Result<u64, ClientError> {
+        let api_download_limit = api::transfer::DownloadLimit {};
+        let s = self.execute(&api_download_limit).await?;
+        let dl_speed: u64 = s.parse().map_err(|e| ClientError::ParseError {
+            endpoint: api_download_limit.relative_path().into_owned(),
+            body: truncate_body(&s),
+            source: Box::new(e),
+        })?;
+        Ok(dl_speed)
+    }
+
+    /// # `/api/v2/transfer/setDownloadLimit`
+    async fn set_download_limit(&self, limit: u64) -> Result<(), ClientError> {
+        let api_set_download_limit = api::transfer::SetDownloadLimit {
+            f: types::transfer::SetDownloadLimitForm { limit },
+        };
+        let _s = self.execute(&api_set_download_limit).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/transfer/uploadLimit`
+    async fn upload_limit(&self) -> Result<u64, ClientError> {
+        let api_upload_limit = api::transfer::UploadLimit {};
+        let s = self.execute(&api_upload_limit).await?;
+        let ul_speed: u64 = s.parse().map_err(|e| ClientError::ParseError {
+            endpoint: api_upload_limit.relative_path().into_owned(),
+            body: truncate_body(&s),
+            source: Box::new(e),
+        })?;
+        Ok(ul_speed)
+    }
+
+    /// # `/api/v2/transfer/setUploadLimit`
+    async fn set_upload_limit(&self, limit: u64) -> Result<(), ClientError> {
+        let api_set_upload_limit = api::transfer::SetUploadLimit {
+            f: types::transfer::SetUploadLimitForm { limit },
+        };
+        let _s = self.execute(&api_set_upload_limit).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/transfer/banPeers`
+    async fn ban_peers<T>(&self, peers: &[T]) -> Result<(), ClientError>
+    where
+        T: AsRef<str> + Send + Sync,
+    {
+        let peers: Vec<String> = peers.iter().map(|p| p.as_ref().to_owned()).collect();
+
+        let f = types::transfer::BanPeersForm { peers };
+        let api_ban_peers = api::transfer::BanPeers { f };
+        let _s = self.execute(&api_ban_peers).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/info`
+    async fn torrents_info(
+        &self,
+        q: &types::torrents::InfoQuery,
+    ) -> Result<types::torrents::InfoResponse, ClientError> {
+        let api_torrents_info = api::torrents::Info { q: q.to_owned() };
+        let de_resp = self.execute(&api_torrents_info).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/torrents/info`
+    ///
+    /// Fetch the torrents matching `q` lazily, `page_size` torrents per request
+    ///
+    /// `q.offset` is where the stream starts and `q.limit` caps the number of torrents yielded,
+    /// a `q.limit` of `0` means no limit as it does for qBittorrent.
+    /// torrents are sorted by `added_on` unless `q.sort` is set, so torrents added
+    /// mid-iteration come last. a sort on a value which changes, like `dlspeed`, is unstable.
+    ///
+    /// each page overlaps the previous one by a torrent, if that torrent moved because
+    /// torrents were added or removed before it, the stream starts over from `q.offset`.
+    /// if it moves again after 3 restarts the stream yields [`ClientError::Other`] and ends.
+    /// a hash is never yielded twice. the stream ends after an error.
+    ///
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use qbit_api_rs::client::{QbitApi, QbitClient};
+    ///
+    /// # async fn run(client: QbitClient) -> Result<(), qbit_api_rs::error::ClientError> {
+    /// let mut torrents = client.torrents_info_stream(&Default::default(), 500);
+    /// while let Some(torrent) = torrents.next().await {
+    ///     println!("{}", torrent?.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn torrents_info_stream(
+        &self,
+        q: &types::torrents::InfoQuery,
+        page_size: u64,
+    ) -> BoxStream<'_, Result<types::torrents::InfoResponseItem, ClientError>> {
+        struct Pages {
+            q: types::torrents::InfoQuery,
+            /// the offset of the next page
+            offset: i64,
+            /// the last hash of the previous page
+            anchor: Option<String>,
+            remaining: Option<u64>,
+            restarts: u32,
+            seen: HashSet<String>,
+            pending: VecDeque<types::torrents::InfoResponseItem>,
+            done: bool,
+        }
+
+        let mut q = q.to_owned();
+        q.sort.get_or_insert(types::torrents::InfoSort::AddedOn);
+        let start = q.offset.unwrap_or(0).max(0);
+        let page_size = page_size.max(1);
+        let init = Pages {
+            remaining: q.limit.take().filter(|limit| *limit > 0),
+            q,
+            offset: start,
+            anchor: None,
+            restarts: 0,
+            seen: HashSet::new(),
+            pending: VecDeque::new(),
+            done: false,
+        };
+
+        let pages = stream::unfold(init, move |mut p| async move {
+            loop {
+                if p.remaining == Some(0) {
+                    return None;
+                }
+                if let Some(torrent) = p.pending.pop_front() {
+                    p.remaining = p.remaining.map(|r| r - 1);
+                    return Some((Ok(torrent), p));
+                }
+                if p.done {
+                    return None;
+                }
+
+                let overlap = p.anchor.is_some() as i64;
+                p.q.offset = Some(p.offset - overlap);
+                p.q.limit = Some(page_size + overlap as u64);
+                let mut page = match self.torrents_info(&p.q).await {
+                    Ok(page) => page,
+                    Err(e) => {
+                        p.done = true;
+                        return Some((Err(e), p));
+                    }
+                };
+                if let Some(anchor) = p.anchor.take() {
+                    if page.first().map(|t| &t.hash) != Some(&anchor) {
+                        // the list shifted, some torrents may have been skipped
+                        if p.restarts == STREAM_RESTARTS {
+                            p.done = true;
+                            let e = ClientError::Other(format!(
+                                "torrents/info changed {} times while paging through it",
+                                STREAM_RESTARTS + 1
+                            ));
+                            return Some((Err(e), p));
+                        }
+                        p.restarts += 1;
+                        p.offset = start;
+                        continue;
+                    }
+                    page.remove(0);
+                }
+
+                p.done = (page.len() as u64) < page_size;
+                p.offset += page.len() as i64;
+                p.anchor = page.last().map(|t| t.hash.clone());
+                let seen = &mut p.seen;
+                p.pending
+                    .extend(page.into_iter().filter(|t| seen.insert(t.hash.clone())));
+            }
+        });
+
+        pages.boxed()
+    }
+
+    /// # `/api/v2/torrents/info`
+    ///
+    /// the torrents matching `selector`, see [`TorrentSelector::split`]
+    async fn torrents_select(
+        &self,
+        selector: &TorrentSelector,
+    ) -> Result<types::torrents::InfoResponse, ClientError> {
+        if selector.selects_nothing() {
+            return Ok(vec![]);
+        }
+        let (q, rest) = selector.split();
+        let mut torrents = self.torrents_info(&q).await?;
+        torrents.retain(|t| rest.matches(t));
+
+        Ok(torrents)
+    }
+
+    /// The hashes of `targets`, a selector is resolved by [`QbitApi::torrents_select`]
+    async fn torrents_targets(&self, targets: TorrentTargets) -> Result<Vec<String>, ClientError> {
+        let hashes = match targets {
+            TorrentTargets::Hashes(hashes) => hashes,
+            TorrentTargets::All => vec!["all".to_owned()],
+            TorrentTargets::Selector(selector) => self
+                .torrents_select(&selector)
+                .await?
+                .into_iter()
+                .map(|t| t.hash)
+                .collect(),
+        };
+
+        Ok(hashes)
+    }
+
+    /// # `/api/v2/torrents/properties`
+    async fn torrents_properties(
+        &self,
+        hash: &str,
+    ) -> Result<types::torrents::PropertiesResponse, ClientError> {
+        let q = types::torrents::PropertiesQuery {
+            hash: hash.to_owned(),
+        };
+        let api_torrents_properties = api::torrents::Properties { q };
+        let de_resp = self.execute(&api_torrents_properties).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/torrents/trackers`
+    async fn torrents_trackers(
+        &self,
+        hash: &str,
+    ) -> Result<types::torrents::TrackersResponse, ClientError> {
+        let q = types::torrents::TrackersQuery {
+            hash: hash.to_owned(),
+        };
+        let api_torrents_trackers = api::torrents::Trackers { q };
+        let de_resp = self.execute(&api_torrents_trackers).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/torrents/webseeds`
+    async fn torrents_webseeds(
+        &self,
+        hash: &str,
+    ) -> Result<types::torrents::WebseedsResponse, ClientError> {
+        let q = types::torrents::WebseedsQuery {
+            hash: hash.to_owned(),
+        };
+        let api_torrents_webseeds = api::torrents::Webseeds { q };
+        let de_resp = self.execute(&api_torrents_webseeds).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/torrents/files`
+    async fn torrents_files(
+        &self,
+        hash: &str,
+        indexes: Option<&[u64]>,
+    ) -> Result<types::torrents::FilesResponse, ClientError> {
+        let indexes = indexes.map(|v| {
+            v.iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<String>>()
+                .join("|")
+        });
+
+        let q = types::torrents::FilesQuery {
+            hash: hash.to_owned(),
+            indexes,
+        };
+        let api_torrents_files = api::torrents::Files { q };
+        let de_resp = self.execute(&api_torrents_files).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/torrents/pieceStates`
+    async fn torrents_piece_states(
+        &self,
+        hash: &str,
+    ) -> Result<types::torrents::PieceStatesResponse, ClientError> {
+        let q = types::torrents::PieceStatesQuery {
+            hash: hash.to_owned(),
+        };
+        let api_torrents_piece_states = api::torrents::PieceStates { q };
+        let de_resp = self.execute(&api_torrents_piece_states).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/torrents/pieceHashes`
+    async fn torrents_piece_hashes(
+        &self,
+        hash: &str,
+    ) -> Result<types::torrents::PieceHashesResponse, ClientError> {
+        let q = types::torrents::PieceHashesQuery {
+            hash: hash.to_owned(),
+        };
+        let api_torrents_piece_hashes = api::torrents::PieceHashes { q };
+        let de_resp = self.execute(&api_torrents_piece_hashes).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/torrents/pause`
+    ///
+    /// accepts hashes or a [`TorrentSelector`], nothing is sent when a selector matches no torrent
+    async fn torrents_pause<T>(&self, targets: T) -> Result<(), ClientError>
+    where
+        T: Into<TorrentTargets> + Send,
+    {
+        let hashes = self.torrents_targets(targets.into()).await?;
+        if hashes.is_empty() {
+            return Ok(());
+        }
+
+        let f = types::torrents::PauseForm { hashes };
+        let api_torrents_pause = api::torrents::Pause { f };
+        let _s = self.execute(&api_torrents_pause).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/resume`
+    ///
+    /// accepts hashes or a [`TorrentSelector`], nothing is sent when a selector matches no torrent
+    async fn torrents_resume<T>(&self, targets: T) -> Result<(), ClientError>
+    where
+        T: Into<TorrentTargets> + Send,
+    {
+        let hashes = self.torrents_targets(targets.into()).await?;
+        if hashes.is_empty() {
+            return Ok(());
+        }
+
+        let f = types::torrents::ResumeForm { hashes };
+        let api_torrents_resume = api::torrents::Resume { f };
+        let _s = self.execute(&api_torrents_resume).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/delete`
+    ///
+    /// accepts hashes or a [`TorrentSelector`], nothing is sent when a selector matches no torrent
+    async fn torrents_delete<T>(&self, targets: T, delete_files: bool) -> Result<(), ClientError>
+    where
+        T: Into<TorrentTargets> + Send,
+    {
+        let hashes = self.torrents_targets(targets.into()).await?;
+        if hashes.is_empty() {
+            return Ok(());
+        }
+
+        let f = types::torrents::DeleteForm {
+            hashes,
+            delete_files,
+        };
+        let api_torrents_delete = api::torrents::Delete { f };
+        let _s = self.execute(&api_torrents_delete).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/recheck`
+    async fn torrents_recheck<H>(&self, hashes: &[H]) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::RecheckForm { hashes };
+        let api_torrents_recheck = api::torrents::Recheck { f };
+        let _s = self.execute(&api_torrents_recheck).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/reannounce`
+    async fn torrents_reannounce<H>(&self, hashes: &[H]) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::ReannounceForm { hashes };
+        let api_torrents_reannounce = api::torrents::Reannounce { f };
+        let _s = self.execute(&api_torrents_reannounce).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/add`
+    async fn torrents_add_by_url<U>(&self, urls: &[U]) -> Result<(), ClientError>
+    where
+        U: AsRef<str> + Send + Sync,
+    {
+        let options = types::torrents::AddTorrentOptions::default();
+        self.torrents_add_by_url_with(urls, &options).await
+    }
+
+    /// # `/api/v2/torrents/add`
+    async fn torrents_add_by_url_with<U>(
+        &self,
+        urls: &[U],
+        options: &types::torrents::AddTorrentOptions,
+    ) -> Result<(), ClientError>
+    where
+        U: AsRef<str> + Send + Sync,
+    {
+        let urls: Vec<String> = urls.iter().map(|u| u.as_ref().to_owned()).collect();
+        self.torrents_add_with(urls, vec![], options).await
+    }
+
+    /// # `/api/v2/torrents/add`
+    async fn torrents_add_magnets(
+        &self,
+        magnets: &[MagnetLink],
+        options: &types::torrents::AddTorrentOptions,
+    ) -> Result<(), ClientError> {
+        let urls = magnets.iter().map(|m| m.to_string()).collect();
+        self.torrents_add_with(urls, vec![], options).await
+    }
+
+    /// # `/api/v2/torrents/add`
+    async fn torrents_add_by_file<F>(&self, files: &[F]) -> Result<(), ClientError>
+    where
+        F: AsRef<Path> + Send + Sync,
+    {
+        let options = types::torrents::AddTorrentOptions::default();
+        self.torrents_add_by_file_with(files, &options).await
+    }
+
+    /// # `/api/v2/torrents/add`
+    async fn torrents_add_by_file_with<F>(
+        &self,
+        files: &[F],
+        options: &types::torrents::AddTorrentOptions,
+    ) -> Result<(), ClientError>
+    where
+        F: AsRef<Path> + Send + Sync,
+    {
+        let sources = files
+            .iter()
+            .map(types::torrents::TorrentSource::path)
+            .collect();
+        self.torrents_add_sources(sources, options).await
+    }
+
+    /// # `/api/v2/torrents/add`
+    ///
+    /// add torrent files held in memory, as file names and contents
+    async fn torrents_add_by_bytes<N>(
+        &self,
+        torrents: &[(N, Bytes)],
+        options: &types::torrents::AddTorrentOptions,
+    ) -> Result<(), ClientError>
+    where
+        N: AsRef<str> + Send + Sync,
+    {
+        let sources = torrents
+            .iter()
+            .map(|(name, content)| types::torrents::TorrentSource::bytes(name, content.clone()))
+            .collect();
+        self.torrents_add_sources(sources, options).await
+    }
+
+    /// # `/api/v2/torrents/add`
+    ///
+    /// add the torrent file read from `reader` to the end, sent as `name`
+    async fn torrents_add_by_reader<N, R>(
+        &self,
+        name: N,
+        reader: R,
+        options: &types::torrents::AddTorrentOptions,
+    ) -> Result<(), ClientError>
+    where
+        N: AsRef<str> + Send,
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        let sources = vec![types::torrents::TorrentSource::reader(name, reader)];
+        self.torrents_add_sources(sources, options).await
+    }
+
+    /// # `/api/v2/torrents/add`
+    ///
+    /// add a mixed batch of urls and torrent files in a single request. files and
+    /// readers are read concurrently without blocking, the first one failing is
+    /// reported as [`ClientError::ReadTorrent`] and nothing is added
+    async fn torrents_add_sources(
+        &self,
+        sources: Vec<types::torrents::TorrentSource>,
+        options: &types::torrents::AddTorrentOptions,
+    ) -> Result<(), ClientError> {
+        let mut urls = vec![];
+        let mut torrents = vec![];
+        for loaded in try_join_all(sources.into_iter().map(load_source)).await? {
+            match loaded {
+                Loaded::Url(url) => urls.push(url),
+                Loaded::File(name, content) => torrents.push((name, content)),
+            }
+        }
+        self.torrents_add_with(urls, torrents, options).await
+    }
+
+    /// # `/api/v2/torrents/add`
+    ///
+    /// add `urls` and `torrents` (file name and content) with `options`, which are
+    /// validated first. the Web API version is requested when some options depend on
+    /// it and [`types::torrents::AddTorrentOptions::api_version`] isn't set
+    async fn torrents_add_with(
+        &self,
+        urls: Vec<String>,
+        torrents: Vec<(String, Vec<u8>)>,
+        options: &types::torrents::AddTorrentOptions,
+    ) -> Result<(), ClientError> {
+        let ta = add_multipart(self, urls, torrents, options).await?;
+        self.torrents_add(ta).await
+    }
+
+    /// # `/api/v2/torrents/add`
+    ///
+    /// add `sources` and report what happened to each of them, in order
+    ///
+    /// hashes are computed locally, from the info dict of torrent files and from the
+    /// `btih` (hex or base32) or `btmh` of magnet links. sources which can't be read or
+    /// parsed fail without being sent. with `confirm`, `torrents/info` is queried before
+    /// adding to tell duplicates apart, and after adding to check the torrents appeared.
+    /// without it, every source sent is reported added when the server answers `Ok.`
+    async fn torrents_add_outcomes(
+        &self,
+        sources: Vec<types::torrents::TorrentSource>,
+        options: &types::torrents::AddTorrentOptions,
+        confirm: bool,
+    ) -> Result<Vec<types::torrents::AddOutcome>, ClientError> {
+        use types::torrents::AddOutcome;
+
+        let loaded = join_all(sources.into_iter().map(load_source)).await;
+        let mut outcomes = vec![None; loaded.len()];
+        // index of the source and its hash, if it can be known
+        let mut sent: Vec<(usize, Option<String>)> = vec![];
+        let mut urls = vec![];
+        let mut torrents = vec![];
+        for (i, loaded) in loaded.into_iter().enumerate() {
+            match loaded {
+                Ok(Loaded::Url(url)) => {
+                    let hash = url.parse::<MagnetLink>().ok().map(|m| m.hash().to_owned());
+                    sent.push((i, hash));
+                    urls.push(url);
+                }
+                Ok(Loaded::File(name, content)) => match metainfo::torrent_hash(&content) {
+                    Ok(hash) => {
+                        sent.push((i, Some(hash)));
+                        torrents.push((name, content));
+                    }
+                    Err(e) => outcomes[i] = Some(AddOutcome::Failed(format!("{}: {}", name, e))),
+                },
+                Err(e) => outcomes[i] = Some(AddOutcome::Failed(e.to_string())),
+            }
+        }
+
+        if !sent.is_empty() {
+            let mut hashes: Vec<String> = sent.iter().filter_map(|(_, h)| h.clone()).collect();
+            hashes.sort();
+            hashes.dedup();
+            let existing = match confirm && !hashes.is_empty() {
+                true => existing_hashes(self, &hashes).await?,
+                false => HashSet::new(),
+            };
+
+            let ta = add_multipart(self, urls, torrents, options).await?;
+            let rejected = match self.execute(&api::torrents::Add { mp: ta }).await {
+                Ok(s) => (s.trim() == "Fails.").then(|| "rejected by the server".to_owned()),
+                Err(e @ ClientError::TorrentFileInvalid { .. }) => Some(e.to_string()),
+                Err(e) => return Err(e),
+            };
+
+            let mut found = HashSet::new();
+            if confirm && rejected.is_none() && hashes.iter().any(|h| !existing.contains(h)) {
+                let expected: Vec<String> = hashes
+                    .iter()
+                    .filter(|h| !existing.contains(*h))
+                    .cloned()
+                    .collect();
+                // torrents can take a moment to show up in `torrents/info`
+                for attempt in 1..=CONFIRM_ATTEMPTS {
+                    found = existing_hashes(self, &expected).await?;
+                    if found.len() == expected.len() || attempt == CONFIRM_ATTEMPTS {
+                        break;
+                    }
+                    tokio::time::sleep(CONFIRM_DELAY * attempt).await;
+                }
+            }
+
+            let mut seen = HashSet::new();
+            for (i, hash) in sent {
+                let outcome = match (&rejected, hash) {
+                    (Some(reason), _) => AddOutcome::Failed(reason.clone()),
+                    (None, None) => AddOutcome::Accepted,
+                    (None, Some(hash)) if existing.contains(&hash) || seen.contains(&hash) => {
+                        AddOutcome::Duplicate(hash)
+                    }
+                    (None, Some(hash)) if confirm && !found.contains(&hash) => {
+                        AddOutcome::Failed(format!("{} not found after adding", hash))
+                    }
+                    (None, Some(hash)) => {
+                        seen.insert(hash.clone());
+                        AddOutcome::Added(hash)
+                    }
+                };
+                outcomes[i] = Some(outcome);
+            }
+        }
+
+        Ok(outcomes.into_iter().flatten().collect())
+    }
+
+    /// # `/api/v2/torrents/add`
+    async fn torrents_add(&self, ta: types::torrents::AddMultipart) -> Result<(), ClientError> {
+        let api_torrents_add = api::torrents::Add { mp: ta };
+        if api_torrents_add.multipart().is_none() {
+            return Err(ClientError::InvalidMultipart("no valid multipart".into()));
+        }
+        let _s = self.execute(&api_torrents_add).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/addTrackers`
+    async fn torrents_add_trackers<H, U>(&self, hash: H, urls: &[U]) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+        U: AsRef<str> + Send + Sync,
+    {
+        let urls: Vec<String> = urls.iter().map(|u| u.as_ref().to_owned()).collect();
+
+        let f = types::torrents::AddTrackersForm {
+            hash: hash.as_ref().to_owned(),
+            urls,
+        };
+        let api_torrents_add_trackers = api::torrents::AddTrackers { f };
+        let _s = self.execute(&api_torrents_add_trackers).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/editTracker`
+    async fn torrents_edit_tracker<H, U>(
+        &self,
+        hash: H,
+        orig_url: U,
+        new_url: U,
+    ) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+        U: AsRef<str> + Send + Sync,
+    {
+        let f = types::torrents::EditTrackerForm {
+            hash: hash.as_ref().to_owned(),
+            orig_url: orig_url.as_ref().to_owned(),
+            new_url: new_url.as_ref().to_owned(),
+        };
+        let api_torrents_edit_tracker = api::torrents::EditTracker { f };
+        let _s = self.execute(&api_torrents_edit_tracker).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/removeTrackers`
+    async fn torrents_remove_trackers<H, U>(&self, hash: H, urls: &[U]) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+        U: AsRef<str> + Send + Sync,
+    {
+        let urls: Vec<String> = urls.iter().map(|u| u.as_ref().to_owned()).collect();
+
+        let f = types::torrents::RemoveTrackersForm {
+            hash: hash.as_ref().to_owned(),
+            urls,
+        };
+        let api_torrents_remove_trackers = api::torrents::RemoveTrackers { f };
+        let _s = self.execute(&api_torrents_remove_trackers).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/addPeers`
+    async fn torrents_add_peers<H, P>(&self, hashes: &[H], peers: &[P]) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+        P: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+        let peers: Vec<String> = peers.iter().map(|p| p.as_ref().to_owned()).collect();
+
+        let f = types::torrents::AddPeersForm { hashes, peers };
+        let api_torrents_add_peers = api::torrents::AddPeers { f };
+        let _s = self.execute(&api_torrents_add_peers).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/increasePrio`
+    async fn torrents_increase_prio<H>(&self, hashes: &[H]) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::IncreasePrioForm { hashes };
+        let api_torrents_increase_prio = api::torrents::IncreasePrio { f };
+        let _s = self.execute(&api_torrents_increase_prio).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/decreasePrio`
+    async fn torrents_decrease_prio<H>(&self, hashes: &[H]) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::DecreasePrioForm { hashes };
+        let api_torrents_decrease_prio = api::torrents::DecreasePrio { f };
+        let _s = self.execute(&api_torrents_decrease_prio).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/topPrio`
+    async fn torrents_top_prio<H>(&self, hashes: &[H]) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::TopPrioForm { hashes };
+        let api_torrents_top_prio = api::torrents::TopPrio { f };
+        let _s = self.execute(&api_torrents_top_prio).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/bottomPrio`
+    async fn torrents_bottom_prio<H>(&self, hashes: &[H]) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::BottomPrioForm { hashes };
+        let api_torrents_bottom_prio = api::torrents::BottomPrio { f };
+        let _s = self.execute(&api_torrents_bottom_prio).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/filePrio`
+    ///
+    /// `indexes` are the `index` field of [`types::torrents::FilesResponseItem`]
+    async fn torrents_file_prio<H>(
+        &self,
+        hash: H,
+        indexes: &[u64],
+        priority: types::torrents::FilesPriority,
+    ) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let f = types::torrents::FilePrioForm {
+            hash: hash.as_ref().to_owned(),
+            id: indexes.to_vec(),
+            priority,
+        };
+        let api_torrents_file_prio = api::torrents::FilePrio { f };
+        let _s = self.execute(&api_torrents_file_prio).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/downloadLimit`
+    async fn torrents_download_limit<H>(
+        &self,
+        hashes: &[H],
+    ) -> Result<types::torrents::DownloadLimitResponse, ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::DownloadLimitForm { hashes };
+        let api_torrents_download_limit = api::torrents::DownloadLimit { f };
+        let de_resp = self.execute(&api_torrents_download_limit).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/torrents/setDownloadLimit`
+    async fn torrents_set_download_limit<H>(
+        &self,
+        hashes: &[H],
+        limit: u64,
+    ) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::SetDownloadLimitForm { hashes, limit };
+        let api_torrents_set_download_limit = api::torrents::SetDownloadLimit { f };
+        let _s = self.execute(&api_torrents_set_download_limit).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/setShareLimits`
+    async fn torrents_set_share_limits<H>(
+        &self,
+        hashes: &[H],
+        ratio_limit: types::torrents::RatioLimit,
+        seeding_time_limit: i64,
+    ) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::SetShareLimitsForm {
+            hashes,
+            ratio_limit,
+            seeding_time_limit,
+        };
+        let api_torrents_set_share_limits = api::torrents::SetShareLimits { f };
+        let _s = self.execute(&api_torrents_set_share_limits).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/uploadLimit`
+    async fn torrents_upload_limit<H>(
+        &self,
+        hashes: &[H],
+    ) -> Result<types::torrents::UploadLimitResponse, ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::UploadLimitForm { hashes };
+        let api_torrents_upload_limit = api::torrents::UploadLimit { f };
+        let de_resp = self.execute(&api_torrents_upload_limit).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/torrents/setUploadLimit`
+    async fn torrents_set_upload_limit<H>(
+        &self,
+        hashes: &[H],
+        limit: u64,
+    ) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::SetUploadLimitForm { hashes, limit };
+        let api_torrents_set_upload_limit = api::torrents::SetUploadLimit { f };
+        let _s = self.execute(&api_torrents_set_upload_limit).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/setLocation`
+    async fn torrents_set_location<H, L>(
+        &self,
+        hashes: &[H],
+        location: L,
+    ) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+        L: AsRef<Path> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::SetLocationForm {
+            hashes,
+            location: location.as_ref().to_string_lossy().to_string(),
+        };
+        let api_torrents_set_location = api::torrents::SetLocation { f };
+        let _s = self.execute(&api_torrents_set_location).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/rename`
+    async fn torernts_rename<H, N>(&self, hash: H, name: N) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+        N: AsRef<str> + Send + Sync,
+    {
+        let f = types::torrents::RenameForm {
+            hash: hash.as_ref().to_owned(),
+            name: name.as_ref().to_owned(),
+        };
+        let api_torrents_rename = api::torrents::Rename { f };
+        let _s = self.execute(&api_torrents_rename).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/setCategory`
+    async fn torernts_set_category<H, C>(
+        &self,
+        hashes: &[H],
+        category: C,
+    ) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+        C: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::SetCategoryForm {
+            hashes,
+            category: category.as_ref().to_owned(),
+        };
+        let api_torrents_set_category = api::torrents::SetCategory { f };
+        let _s = self.execute(&api_torrents_set_category).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/categories`
+    async fn torrents_categories(
+        &self,
+    ) -> Result<types::torrents::CategoriesResponse, ClientError> {
+        let api_torrents_categories = api::torrents::Categories {};
+        let de_resp = self.execute(&api_torrents_categories).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/torrents/createCategory`
+    async fn torrents_create_category<C, P>(
+        &self,
+        category: C,
+        save_path: P,
+    ) -> Result<(), ClientError>
+    where
+        C: AsRef<str> + Send + Sync,
+        P: AsRef<Path> + Send + Sync,
+    {
+        let f = types::torrents::CreateCategoryForm {
+            category: category.as_ref().to_owned(),
+            save_path: save_path.as_ref().to_string_lossy().to_string(),
+        };
+        let api_torrents_create_category = api::torrents::CreateCategory { f };
+        let _s = self.execute(&api_torrents_create_category).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/editCategory`
+    async fn torrents_edit_category<C, P>(
+        &self,
+        category: C,
+        save_path: P,
+    ) -> Result<(), ClientError>
+    where
+        C: AsRef<str> + Send + Sync,
+        P: AsRef<Path> + Send + Sync,
+    {
+        let f = types::torrents::EditCategoryForm {
+            category: category.as_ref().to_owned(),
+            save_path: save_path.as_ref().to_string_lossy().to_string(),
+        };
+        let api_torrents_edit_category = api::torrents::EditCategory { f };
+        let _s = self.execute(&api_torrents_edit_category).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/removeCategories`
+    async fn torrents_remove_categories<C>(&self, categories: &[C]) -> Result<(), ClientError>
+    where
+        C: AsRef<str> + Send + Sync,
+    {
+        let categories: Vec<String> = categories.iter().map(|c| c.as_ref().to_owned()).collect();
+
+        let f = types::torrents::RemoveCategoriesForm { categories };
+        let api_torrents_remove_categories = api::torrents::RemoveCategories { f };
+        let _s = self.execute(&api_torrents_remove_categories).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/addTags`
+    ///
+    /// accepts hashes or a [`TorrentSelector`], nothing is sent when a selector matches no torrent
+    async fn torrents_add_tags<H, T>(&self, targets: H, tags: &[T]) -> Result<(), ClientError>
+    where
+        H: Into<TorrentTargets> + Send,
+        T: AsRef<str> + Send + Sync,
+    {
+        let hashes = self.torrents_targets(targets.into()).await?;
+        if hashes.is_empty() {
+            return Ok(());
+        }
+        let tags: Vec<String> = tags.iter().map(|t| t.as_ref().to_owned()).collect();
+
+        let f = types::torrents::AddTagsForm { hashes, tags };
+        let api_torrents_add_tags = api::torrents::AddTags { f };
+        let _s = self.execute(&api_torrents_add_tags).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/removeTags`
+    ///
+    /// accepts hashes or a [`TorrentSelector`], nothing is sent when a selector matches no torrent
+    async fn torrents_remove_tags<H, T>(&self, targets: H, tags: &[T]) -> Result<(), ClientError>
+    where
+        H: Into<TorrentTargets> + Send,
+        T: AsRef<str> + Send + Sync,
+    {
+        let hashes = self.torrents_targets(targets.into()).await?;
+        if hashes.is_empty() {
+            return Ok(());
+        }
+        let tags: Vec<String> = tags.iter().map(|t| t.as_ref().to_owned()).collect();
+
+        let f = types::torrents::RemoveTagsForm { hashes, tags };
+        let api_torrents_remove_tags = api::torrents::RemoveTags { f };
+        let _s = self.execute(&api_torrents_remove_tags).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/tags`
+    async fn torrents_tags(&self) -> Result<types::torrents::TagsResponse, ClientError> {
+        let api_torrents_tags = api::torrents::Tags {};
+        let de_resp = self.execute(&api_torrents_tags).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/torrents/createTags`
+    async fn torrens_create_tags<T>(&self, tags: &[T]) -> Result<(), ClientError>
+    where
+        T: AsRef<str> + Send + Sync,
+    {
+        let tags: Vec<String> = tags.iter().map(|t| t.as_ref().to_owned()).collect();
+
+        let f = types::torrents::CreateTagsForm { tags };
+        let api_torrents_create_tags = api::torrents::CreateTags { f };
+        let _s = self.execute(&api_torrents_create_tags).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/deleteTags`
+    async fn torrents_delete_tags<T>(&self, tags: &[T]) -> Result<(), ClientError>
+    where
+        T: AsRef<str> + Send + Sync,
+    {
+        let tags: Vec<String> = tags.iter().map(|t| t.as_ref().to_owned()).collect();
+
+        let f = types::torrents::DeleteTagsForm { tags };
+        let api_torrents_delete_tags = api::torrents::DeleteTags { f };
+        let _s = self.execute(&api_torrents_delete_tags).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/setAutoManagement`
+    async fn torrents_set_auto_management<H>(
+        &self,
+        hashes: &[H],
+        enable: bool,
+    ) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::SetAutoManagementForm { hashes, enable };
+        let api_torrents_set_automanagement = api::torrents::SetAutoManagement { f };
+        let _s = self.execute(&api_torrents_set_automanagement).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/toggleSequentialDownload`
+    async fn torrents_toggle_sequential_download<H>(&self, hashes: &[H]) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::ToggleSequentialDownloadForm { hashes };
+        let api_torrents_toggle_sequential_download = api::torrents::ToggleSequentialDownload { f };
+        let _s = self
+            .execute(&api_torrents_toggle_sequential_download)
+            .await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/toggleFirstLastPiecePrio`
+    async fn torrents_toggle_first_last_piece_prio<H>(
+        &self,
+        hashes: &[H],
+    ) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::ToggleFirstLastPiecePrioForm { hashes };
+        let api_torrents_toggle_first_last_piece_prio =
+            api::torrents::ToggleFirstLastPiecePrio { f };
+        let _s = self
+            .execute(&api_torrents_toggle_first_last_piece_prio)
+            .await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/setForceStart`
+    async fn torrents_set_force_start<H>(
+        &self,
+        hashes: &[H],
+        value: bool,
+    ) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::SetForceStartForm { hashes, value };
+        let api_torrents_set_force_start = api::torrents::SetForceStart { f };
+        let _s = self.execute(&api_torrents_set_force_start).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/setSuperSeeding`
+    async fn torrents_set_super_seeding<H>(
+        &self,
+        hashes: &[H],
+        value: bool,
+    ) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+    {
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_owned()).collect();
+
+        let f = types::torrents::SetSuperSeedingForm { hashes, value };
+        let api_torrents_set_super_seeding = api::torrents::SetSuperSeeding { f };
+        let _s = self.execute(&api_torrents_set_super_seeding).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/renameFile`
+    async fn torrents_rename_file<H, P>(
+        &self,
+        hash: H,
+        old_path: P,
+        new_path: P,
+    ) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+        P: AsRef<Path> + Send + Sync,
+    {
+        let f = types::torrents::RenameFileForm {
+            hash: hash.as_ref().to_owned(),
+            old_path: old_path.as_ref().to_string_lossy().to_string(),
+            new_path: new_path.as_ref().to_string_lossy().to_string(),
+        };
+        let api_torrents_rename_file = api::torrents::RenameFile { f };
+        let _s = self.execute(&api_torrents_rename_file).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/torrents/renameFolder`
+    async fn torrents_rename_folder<H, P>(
+        &self,
+        hash: H,
+        old_path: P,
+        new_path: P,
+    ) -> Result<(), ClientError>
+    where
+        H: AsRef<str> + Send + Sync,
+        P: AsRef<Path> + Send + Sync,
+    {
+        let f = types::torrents::RenameFolderForm {
+            hash: hash.as_ref().to_owned(),
+            old_path: old_path.as_ref().to_string_lossy().to_string(),
+            new_path: new_path.as_ref().to_string_lossy().to_string(),
+        };
+        let api_torrents_rename_folder = api::torrents::RenameFolder { f };
+        let _s = self.execute(&api_torrents_rename_folder).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/rss/addFolder`
+    async fn rss_add_folder<P>(&self, path: P) -> Result<(), ClientError>
+    where
+        P: AsRef<str> + Send + Sync,
+    {
+        let f = types::rss::AddFolderForm {
+            path: path.as_ref().to_owned(),
+        };
+        let api_rss_add_folder = api::rss::AddFolder { f };
+        let _s = self.execute(&api_rss_add_folder).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/rss/addFeed`
+    async fn rss_add_feed<U, P>(&self, url: U, path: Option<P>) -> Result<(), ClientError>
+    where
+        U: AsRef<str> + Send + Sync,
+        P: AsRef<str> + Send + Sync,
+    {
+        let f = types::rss::AddFeedForm {
+            url: url.as_ref().to_owned(),
+            path: path.map(|p| p.as_ref().to_owned()),
+        };
+        let api_rss_add_feed = api::rss::AddFeed { f };
+        let _s = self.execute(&api_rss_add_feed).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/rss/removeItem`
+    async fn rss_remove_item<P>(&self, path: P) -> Result<(), ClientError>
+    where
+        P: AsRef<str> + Send + Sync,
+    {
+        let f = types::rss::RemoveItemForm {
+            path: path.as_ref().to_owned(),
+        };
+        let api_rss_remove_item = api::rss::RemoveItem { f };
+        let _s = self.execute(&api_rss_remove_item).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/rss/moveItem`
+    async fn rss_move_item<P>(&self, item_path: P, dest_path: P) -> Result<(), ClientError>
+    where
+        P: AsRef<str> + Send + Sync,
+    {
+        let f = types::rss::MoveItemForm {
+            item_path: item_path.as_ref().to_owned(),
+            dest_path: dest_path.as_ref().to_owned(),
+        };
+        let api_rss_move_item = api::rss::MoveItem { f };
+        let _s = self.execute(&api_rss_move_item).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/rss/items`
+    async fn rss_items(&self, with_data: bool) -> Result<types::rss::ItemsResponse, ClientError> {
+        let q = types::rss::ItemsQuery { with_data };
+        let api_rss_items = api::rss::Items { q };
+        let de_resp = self.execute(&api_rss_items).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/rss/markAsRead`
+    async fn rss_mark_as_read<P, A>(
+        &self,
+        item_path: P,
+        article_id: Option<A>,
+    ) -> Result<(), ClientError>
+    where
+        P: AsRef<str> + Send + Sync,
+        A: AsRef<str> + Send + Sync,
+    {
+        let f = types::rss::MarkAsReadForm {
+            item_path: item_path.as_ref().to_owned(),
+            article_id: article_id.map(|a| a.as_ref().to_owned()),
+        };
+        let api_rss_mark_as_read = api::rss::MarkAsRead { f };
+        let _s = self.execute(&api_rss_mark_as_read).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/rss/refreshItem`
+    async fn rss_refresh_item<P>(&self, item_path: P) -> Result<(), ClientError>
+    where
+        P: AsRef<str> + Send + Sync,
+    {
+        let f = types::rss::RefreshItemForm {
+            item_path: item_path.as_ref().to_owned(),
+        };
+        let api_rss_refresh_item = api::rss::RefreshItem { f };
+        let _s = self.execute(&api_rss_refresh_item).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/rss/setRule`
+    async fn rss_set_rule<N>(
+        &self,
+        rule_name: N,
+        rule_def: &types::rss::Rule,
+    ) -> Result<(), ClientError>
+    where
+        N: AsRef<str> + Send + Sync,
+    {
+        let f = types::rss::SetRuleForm {
+            rule_name: rule_name.as_ref().to_owned(),
+            rule_def: rule_def.to_owned(),
+        };
+        let api_rss_set_rule = api::rss::SetRule { f };
+        let _s = self.execute(&api_rss_set_rule).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/rss/renameRule`
+    async fn rss_rename_rule<N>(&self, rule_name: N, new_rule_name: N) -> Result<(), ClientError>
+    where
+        N: AsRef<str> + Send + Sync,
+    {
+        let f = types::rss::RenameRuleForm {
+            rule_name: rule_name.as_ref().to_owned(),
+            new_rule_name: new_rule_name.as_ref().to_owned(),
+        };
+        let api_rss_rename_rule = api::rss::RenameRule { f };
+        let _s = self.execute(&api_rss_rename_rule).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/rss/removeRule`
+    async fn rss_remove_rule<N>(&self, rule_name: N) -> Result<(), ClientError>
+    where
+        N: AsRef<str> + Send + Sync,
+    {
+        let f = types::rss::RemoveRuleForm {
+            rule_name: rule_name.as_ref().to_owned(),
+        };
+        let api_rss_remove_rule = api::rss::RemoveRule { f };
+        let _s = self.execute(&api_rss_remove_rule).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/rss/rules`
+    async fn rss_rules(&self) -> Result<types::rss::RulesResponse, ClientError> {
+        let api_rss_rules = api::rss::Rules {};
+        let de_resp = self.execute(&api_rss_rules).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/rss/matchingArticles`
+    async fn rss_matching_articles<N>(
+        &self,
+        rule_name: N,
+    ) -> Result<types::rss::MatchingArticlesResponse, ClientError>
+    where
+        N: AsRef<str> + Send + Sync,
+    {
+        let q = types::rss::MatchingArticlesQuery {
+            rule_name: rule_name.as_ref().to_owned(),
+        };
+        let api_rss_matching_articles = api::rss::MatchingArticles { q };
+        let de_resp = self.execute(&api_rss_matching_articles).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/search/start`
+    ///
+    /// the returned [`SearchJob`] streams the results, and stops the job when dropped.
+    /// `plugins` and `category` also accept strings, e.g. `"all"` or `"movies"`
+    async fn search_start<T, P, C>(
+        &self,
+        pattern: T,
+        plugins: P,
+        category: C,
+    ) -> Result<SearchJob<Self>, ClientError>
+    where
+        T: AsRef<str> + Send + Sync,
+        P: Into<types::search::PluginSelection> + Send,
+        C: Into<types::search::SearchCategory> + Send,
+        Self: Sized + Clone + 'static,
+    {
+        let f = types::search::StartForm {
+            pattern: pattern.as_ref().to_owned(),
+            plugins: plugins.into(),
+            category: category.into(),
+        };
+        let api_search_start = api::search::Start { f };
+        let de_resp = self.execute(&api_search_start).await?;
+
+        Ok(SearchJob::new(self.clone(), de_resp.id))
+    }
+
+    /// # `/api/v2/search/start`
+    ///
+    /// like [`QbitApi::search_start`], but fails with [`ClientError::InvalidSearch`]
+    /// before starting when a plugin isn't installed and enabled, or the category
+    /// isn't supported by any of the plugins, see [`search::validate_start`]
+    async fn search_start_checked<T, P, C>(
+        &self,
+        pattern: T,
+        plugins: P,
+        category: C,
+    ) -> Result<SearchJob<Self>, ClientError>
+    where
+        T: AsRef<str> + Send + Sync,
+        P: Into<types::search::PluginSelection> + Send,
+        C: Into<types::search::SearchCategory> + Send,
+        Self: Sized + Clone + 'static,
+    {
+        let plugins = plugins.into();
+        let category = category.into();
+        let installed = self.search_plugins().await?;
+        search::validate_start(&installed, &plugins, &category)?;
+
+        self.search_start(pattern, plugins, category).await
+    }
+
+    /// # `/api/v2/search/stop`
+    async fn search_stop(&self, id: u64) -> Result<(), ClientError> {
+        let f = types::search::StopForm { id };
+        let api_search_stop = api::search::Stop { f };
+        let _s = self.execute(&api_search_stop).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/search/status`
+    async fn search_status(
+        &self,
+        id: Option<u64>,
+    ) -> Result<types::search::StatusResponse, ClientError> {
+        let q = types::search::StatusQuery { id };
+        let api_search_status = api::search::Status { q };
+        let de_resp = self.execute(&api_search_status).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/search/results`
+    async fn search_results(
+        &self,
+        id: u64,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<types::search::ResultsResponse, ClientError> {
+        let q = types::search::ResultsQuery { id, limit, offset };
+        let api_search_results = api::search::Results { q };
+        let de_resp = self.execute(&api_search_results).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/search/delete`
+    async fn search_delete(&self, id: u64) -> Result<(), ClientError> {
+        let f = types::search::DeleteForm { id };
+        let api_search_delete = api::search::Delete { f };
+        let _s = self.execute(&api_search_delete).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/search/plugins`
+    async fn search_plugins(&self) -> Result<types::search::PluginsResponse, ClientError> {
+        let api_search_plugins = api::search::Plugins {};
+        let de_resp = self.execute(&api_search_plugins).await?;
+
+        Ok(de_resp)
+    }
+
+    /// # `/api/v2/search/installPlugin`
+    async fn search_install_plugin<S>(&self, sources: &[S]) -> Result<(), ClientError>
+    where
+        S: AsRef<str> + Send + Sync,
+    {
+        let sources: Vec<String> = sources.iter().map(|s| s.as_ref().to_owned()).collect();
+
+        let f = types::search::InstallPluginForm { sources };
+        let api_search_install_plugin = api::search::InstallPlugin { f };
+        let _s = self.execute(&api_search_install_plugin).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/search/uninstallPlugin`
+    async fn search_uninstall_plugin<N>(&self, names: &[N]) -> Result<(), ClientError>
+    where
+        N: AsRef<str> + Send + Sync,
+    {
+        let names: Vec<String> = names.iter().map(|n| n.as_ref().to_owned()).collect();
+
+        let f = types::search::UninstallPluginForm { names };
+        let api_search_uninstall_plugin = api::search::UninstallPlugin { f };
+        let _s = self.execute(&api_search_uninstall_plugin).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/search/enablePlugin`
+    async fn search_enable_plugin<N>(&self, names: &[N], enable: bool) -> Result<(), ClientError>
+    where
+        N: AsRef<str> + Send + Sync,
+    {
+        let names: Vec<String> = names.iter().map(|n| n.as_ref().to_owned()).collect();
+
+        let f = types::search::EnablePluginForm { names, enable };
+        let api_search_enable_plugin = api::search::EnablePlugin { f };
+        let _s = self.execute(&api_search_enable_plugin).await?;
+
+        Ok(())
+    }
+
+    /// # `/api/v2/search/updatePlugins`
+    async fn search_update_plugins(&self) -> Result<(), ClientError> {
+        let api_search_update_plugins = api::search::UpdatePlugins {};
+        let _s = self.execute(&api_search_update_plugins).await?;
+
+        Ok(())
+    }
+}
+
+/// how many times [`QbitApi::torrents_info_stream`] starts over before giving up
+const STREAM_RESTARTS: u32 = 3;
+
+/// how many times [`QbitApi::torrents_add_outcomes`] looks for the added torrents
+const CONFIRM_ATTEMPTS: u32 = 5;
+const CONFIRM_DELAY: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// validate `options` and build the multipart, requesting the Web API version if needed
+async fn add_multipart<A>(
+    api: &A,
+    urls: Vec<String>,
+    torrents: Vec<(String, Vec<u8>)>,
+    options: &types::torrents::AddTorrentOptions,
+) -> Result<types::torrents::AddMultipart, ClientError>
+where
+    A: QbitApi + ?Sized,
+{
+    options
+        .validate()
+        .map_err(|e| ClientError::InvalidMultipart(e.to_string()))?;
+    let version = if options.needs_api_version() {
+        let s = api.app_webapi_version().await?;
+        let version = s.parse().map_err(|e| ClientError::ParseError {
+            endpoint: api::app::WebApiVersion {}.relative_path().into_owned(),
+            body: truncate_body(&s),
+            source: Box::new(e),
+        })?;
+        Some(version)
+    } else {
+        None
+    };
+    options
+        .to_multipart(urls, torrents, version)
+        .map_err(|e| ClientError::InvalidMultipart(e.to_string()))
+}
+
+/// which of `hashes` the server has
+async fn existing_hashes<A>(api: &A, hashes: &[String]) -> Result<HashSet<String>, ClientError>
+where
+    A: QbitApi + ?Sized,
+{
+    let query = types::torrents::InfoQuery {
+        hashes: Some(hashes.to_vec()),
+        ..Default::default()
+    };
+    let torrents = api.torrents_info(&query).await?;
+    Ok(torrents
+        .into_iter()
+        .map(|t| t.hash.to_lowercase())
+        .collect())
+}
+
+/// a [`types::torrents::TorrentSource`] read into memory
+enum Loaded {
+    Url(String),
+    File(String, Vec<u8>),
+}
+
+async fn load_source(source: types::torrents::TorrentSource) -> Result<Loaded, ClientError> {
+    use types::torrents::TorrentSource;
+
+    let read_error = |path: &str, source: io::Error| ClientError::ReadTorrent {
+        path: path.to_owned(),
+        source,
+    };
+    match source {
+        TorrentSource::Url(url) => Ok(Loaded::Url(url)),
+        TorrentSource::Bytes { name, content } => Ok(Loaded::File(name, content.to_vec())),
+        TorrentSource::Path(path) => {
+            let display = path.to_string_lossy();
+            let name = path.file_name().ok_or_else(|| {
+                read_error(
+                    &display,
+                    io::Error::new(io::ErrorKind::InvalidInput, "no file name"),
+                )
+            })?;
+            let content = tokio::fs::read(&path)
+                .await
+                .map_err(|e| read_error(&display, e))?;
+            Ok(Loaded::File(name.to_string_lossy().into_owned(), content))
+        }
+        TorrentSource::Reader { name, mut reader } => {
+            let mut content = vec![];
+            reader
+                .read_to_end(&mut content)
+                .await
+                .map_err(|e| read_error(&name, e))?;
+            Ok(Loaded::File(name, content))
+        }
+    }
+}
@@ -0,0 +1,430 @@
+//! A pool of named qBittorrent instances
+//!
+//! reads are sent to every instance concurrently and merged, writes on torrents are
+//! routed to the instances owning them. pause, resume, delete, tags, category and file
+//! priority have routed wrappers, any other write is routed by [`QbitCluster::route`].
+//! a failing instance doesn't fail the whole call, its error is reported in
+//! [`ClusterResponse::failures`] next to the data of the others.
+//!
+//! ```no_run
+//! use qbit_api_rs::client::{QbitApi, QbitClient};
+//! use qbit_api_rs::cluster::QbitCluster;
+//!
+//! # async fn run() -> Result<(), qbit_api_rs::error::ClientError> {
+//! let mut cluster = QbitCluster::new();
+//! cluster.insert("nas", QbitClient::new_with_user_pwd("http://nas:8080", "admin", "pwd")?);
+//! cluster.insert("seedbox", QbitClient::new_with_user_pwd("https://seedbox", "admin", "pwd")?);
+//! cluster.auth_login().await;
+//!
+//! let resp = cluster.torrents_info(&Default::default()).await;
+//! for (instance, e) in resp.failures.iter() {
+//!     eprintln!("{} is unavailable: {}", instance, e);
+//! }
+//! for t in resp.data.iter() {
+//!     println!("{}: {}", t.instance, t.torrent.name);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::{QbitApi, QbitClient};
+use crate::error::ClientError;
+use crate::types::torrents::{CategoriesDetails, FilesPriority, InfoQuery, InfoResponseItem};
+use crate::types::transfer;
+use futures_util::future::join_all;
+use std::collections::BTreeMap;
+use std::future::Future;
+
+/// The merged data of the instances that succeeded, and the errors of those that failed
+#[derive(Debug)]
+pub struct ClusterResponse<T> {
+    pub data: T,
+    /// errors keyed by instance name
+    pub failures: BTreeMap<String, ClientError>,
+    /// hashes which no instance owns, only set by calls routed by hash
+    pub not_found: Vec<String>,
+}
+
+impl<T> ClusterResponse<T> {
+    /// every instance succeeded and every hash was found
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty() && self.not_found.is_empty()
+    }
+
+    fn discard_data(self) -> ClusterResponse<()> {
+        ClusterResponse {
+            data: (),
+            failures: self.failures,
+            not_found: self.not_found,
+        }
+    }
+}
+
+/// # `/api/v2/torrents/info`
+/// [`QbitCluster::torrents_info`]
+#[derive(Debug, Clone)]
+pub struct ClusterTorrent {
+    /// name of the instance owning the torrent
+    pub instance: String,
+    pub torrent: InfoResponseItem,
+}
+
+/// # `/api/v2/transfer/info`
+/// [`QbitCluster::transfer_info`]
+#[derive(Debug, Clone, Default)]
+pub struct ClusterTransferInfo {
+    /// sum of the speeds and data of all instances
+    pub dl_info_speed: u64,
+    pub dl_info_data: u64,
+    pub up_info_speed: u64,
+    pub up_info_data: u64,
+    /// keyed by instance name
+    pub instances: BTreeMap<String, transfer::InfoResponse>,
+}
+
+/// A pool of named qBittorrent instances, see the [module docs](self)
+#[derive(Debug)]
+pub struct QbitCluster<A = QbitClient> {
+    instances: BTreeMap<String, A>,
+}
+
+impl<A> Default for QbitCluster<A> {
+    fn default() -> Self {
+        Self {
+            instances: BTreeMap::new(),
+        }
+    }
+}
+
+impl<A> QbitCluster<A>
+where
+    A: QbitApi,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an instance, returns the instance previously added with `name`
+    pub fn insert<N>(&mut self, name: N, api: A) -> Option<A>
+    where
+        N: AsRef<str>,
+    {
+        self.instances.insert(name.as_ref().to_owned(), api)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<A> {
+        self.instances.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&A> {
+        self.instances.get(name)
+    }
+
+    /// names of the instances, in order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.instances.keys().map(|n| n.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.instances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instances.is_empty()
+    }
+
+    /// Run `f` on every instance concurrently
+    pub async fn fan_out<'a, F, Fut, T>(&'a self, f: F) -> ClusterResponse<BTreeMap<String, T>>
+    where
+        F: Fn(&'a A) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let results = join_all(self.instances.values().map(f)).await;
+
+        let mut data = BTreeMap::new();
+        let mut failures = BTreeMap::new();
+        for (name, result) in self.instances.keys().zip(results) {
+            match result {
+                Ok(value) => {
+                    data.insert(name.clone(), value);
+                }
+                Err(e) => {
+                    failures.insert(name.clone(), e);
+                }
+            }
+        }
+        ClusterResponse {
+            data,
+            failures,
+            not_found: vec![],
+        }
+    }
+
+    /// # `/api/v2/auth/login`
+    ///
+    /// login every instance
+    pub async fn auth_login(&self) -> ClusterResponse<()> {
+        let resp = self.fan_out(|api| api.auth_login()).await;
+        ClusterResponse {
+            data: (),
+            failures: resp.failures,
+            not_found: vec![],
+        }
+    }
+
+    /// # `/api/v2/torrents/info`
+    ///
+    /// the torrents of all instances, in the order of instance names.
+    /// `limit` and `offset` of the query apply to each instance
+    pub async fn torrents_info(&self, query: &InfoQuery) -> ClusterResponse<Vec<ClusterTorrent>> {
+        let resp = self.fan_out(|api| api.torrents_info(query)).await;
+        let torrents = resp
+            .data
+            .into_iter()
+            .flat_map(|(instance, torrents)| {
+                torrents.into_iter().map(move |torrent| ClusterTorrent {
+                    instance: instance.clone(),
+                    torrent,
+                })
+            })
+            .collect();
+        ClusterResponse {
+            data: torrents,
+            failures: resp.failures,
+            not_found: vec![],
+        }
+    }
+
+    /// # `/api/v2/transfer/info`
+    pub async fn transfer_info(&self) -> ClusterResponse<ClusterTransferInfo> {
+        let resp = self.fan_out(|api| api.transfer_info()).await;
+        let mut info = ClusterTransferInfo::default();
+        for i in resp.data.values() {
+            info.dl_info_speed += i.dl_info_speed;
+            info.dl_info_data += i.dl_info_data;
+            info.up_info_speed += i.up_info_speed;
+            info.up_info_data += i.up_info_data;
+        }
+        info.instances = resp.data;
+        ClusterResponse {
+            data: info,
+            failures: resp.failures,
+            not_found: vec![],
+        }
+    }
+
+    /// # `/api/v2/torrents/categories`
+    ///
+    /// keyed by category name, then by the names of the instances having it
+    pub async fn torrents_categories(
+        &self,
+    ) -> ClusterResponse<BTreeMap<String, BTreeMap<String, CategoriesDetails>>> {
+        let resp = self.fan_out(|api| api.torrents_categories()).await;
+        let mut categories: BTreeMap<String, BTreeMap<String, CategoriesDetails>> = BTreeMap::new();
+        for (instance, cs) in resp.data {
+            for (name, details) in cs {
+                categories
+                    .entry(name)
+                    .or_default()
+                    .insert(instance.clone(), details);
+            }
+        }
+        ClusterResponse {
+            data: categories,
+            failures: resp.failures,
+            not_found: vec![],
+        }
+    }
+
+    /// Find the instances owning each of `hashes`
+    ///
+    /// keyed by instance name, a hash on several instances is listed under each of them.
+    /// hashes owned by no instance are in `not_found`. no instance is called if `hashes` is empty
+    pub async fn owners<H>(&self, hashes: &[H]) -> ClusterResponse<BTreeMap<String, Vec<String>>>
+    where
+        H: AsRef<str>,
+    {
+        // `hashes=` would select every torrent
+        if hashes.is_empty() {
+            return ClusterResponse {
+                data: BTreeMap::new(),
+                failures: BTreeMap::new(),
+                not_found: vec![],
+            };
+        }
+        let hashes: Vec<String> = hashes.iter().map(|h| h.as_ref().to_lowercase()).collect();
+        let query = InfoQuery {
+            hashes: Some(hashes.clone()),
+            ..Default::default()
+        };
+        let resp = self.fan_out(|api| api.torrents_info(&query)).await;
+
+        let mut owners: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut owned = vec![];
+        for (instance, torrents) in resp.data {
+            for t in torrents {
+                // a torrent on several instances is owned by each of them
+                if !owned.contains(&t.hash) {
+                    owned.push(t.hash.clone());
+                }
+                owners.entry(instance.clone()).or_default().push(t.hash);
+            }
+        }
+        let not_found = hashes.into_iter().filter(|h| !owned.contains(h)).collect();
+        ClusterResponse {
+            data: owners,
+            failures: resp.failures,
+            not_found,
+        }
+    }
+
+    /// Run `f` on every instance owning some of `hashes`, with the hashes it owns
+    ///
+    /// a hash on several instances is sent to each of them
+    ///
+    /// an instance which fails the owner lookup is reported in `failures`,
+    /// and its hashes are in `not_found` too
+    pub async fn route<'a, H, F, Fut, T>(
+        &'a self,
+        hashes: &[H],
+        f: F,
+    ) -> ClusterResponse<BTreeMap<String, T>>
+    where
+        H: AsRef<str>,
+        F: Fn(&'a A, Vec<String>) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let owners = self.owners(hashes).await;
+        let calls = owners.data.into_iter().filter_map(|(instance, hashes)| {
+            let api = self.instances.get(&instance)?;
+            let fut = f(api, hashes);
+            Some(async move { (instance, fut.await) })
+        });
+        let results = join_all(calls).await;
+
+        let mut data = BTreeMap::new();
+        let mut failures = owners.failures;
+        for (instance, result) in results {
+            match result {
+                Ok(value) => {
+                    data.insert(instance, value);
+                }
+                Err(e) => {
+                    failures.insert(instance, e);
+                }
+            }
+        }
+        ClusterResponse {
+            data,
+            failures,
+            not_found: owners.not_found,
+        }
+    }
+
+    /// # `/api/v2/torrents/pause`
+    pub async fn torrents_pause<H>(&self, hashes: &[H]) -> ClusterResponse<()>
+    where
+        H: AsRef<str>,
+    {
+        let resp = self
+            .route(hashes, |api, hashes| async move {
+                api.torrents_pause(&hashes).await
+            })
+            .await;
+        resp.discard_data()
+    }
+
+    /// # `/api/v2/torrents/resume`
+    pub async fn torrents_resume<H>(&self, hashes: &[H]) -> ClusterResponse<()>
+    where
+        H: AsRef<str>,
+    {
+        let resp = self
+            .route(hashes, |api, hashes| async move {
+                api.torrents_resume(&hashes).await
+            })
+            .await;
+        resp.discard_data()
+    }
+
+    /// # `/api/v2/torrents/delete`
+    pub async fn torrents_delete<H>(&self, hashes: &[H], delete_files: bool) -> ClusterResponse<()>
+    where
+        H: AsRef<str>,
+    {
+        let resp = self
+            .route(hashes, |api, hashes| async move {
+                api.torrents_delete(&hashes, delete_files).await
+            })
+            .await;
+        resp.discard_data()
+    }
+
+    /// # `/api/v2/torrents/addTags`
+    pub async fn torrents_add_tags<H, T>(&self, hashes: &[H], tags: &[T]) -> ClusterResponse<()>
+    where
+        H: AsRef<str>,
+        T: AsRef<str> + Send + Sync,
+    {
+        let resp = self
+            .route(hashes, |api, hashes| async move {
+                api.torrents_add_tags(&hashes, tags).await
+            })
+            .await;
+        resp.discard_data()
+    }
+
+    /// # `/api/v2/torrents/removeTags`
+    pub async fn torrents_remove_tags<H, T>(&self, hashes: &[H], tags: &[T]) -> ClusterResponse<()>
+    where
+        H: AsRef<str>,
+        T: AsRef<str> + Send + Sync,
+    {
+        let resp = self
+            .route(hashes, |api, hashes| async move {
+                api.torrents_remove_tags(&hashes, tags).await
+            })
+            .await;
+        resp.discard_data()
+    }
+
+    /// # `/api/v2/torrents/setCategory`
+    ///
+    /// the category must exist on every instance owning some of `hashes`
+    pub async fn torrents_set_category<H, C>(
+        &self,
+        hashes: &[H],
+        category: C,
+    ) -> ClusterResponse<()>
+    where
+        H: AsRef<str>,
+        C: AsRef<str>,
+    {
+        let category = category.as_ref();
+        let resp = self
+            .route(hashes, |api, hashes| async move {
+                api.torernts_set_category(&hashes, category).await
+            })
+            .await;
+        resp.discard_data()
+    }
+
+    /// # `/api/v2/torrents/filePrio`
+    pub async fn torrents_file_prio<H>(
+        &self,
+        hash: H,
+        indexes: &[u64],
+        priority: FilesPriority,
+    ) -> ClusterResponse<()>
+    where
+        H: AsRef<str>,
+    {
+        let resp = self
+            .route(&[hash], |api, hashes| {
+                let priority = priority.clone();
+                async move { api.torrents_file_prio(&hashes[0], indexes, priority).await }
+            })
+            .await;
+        resp.discard_data()
+    }
+}
@@ -1,8 +1,12 @@
+use reqwest::{Method, StatusCode};
 use thiserror::Error;
 
+/// At most this many bytes of a response body are kept in a [`ClientError`]
+pub const MAX_BODY_LEN: usize = 512;
+
 #[derive(Error, Debug)]
 pub enum ClientError {
-    #[error("Initialize Error")]
+    #[error("Initialize Error: {0}")]
     Initialize(String),
     #[error("Token expired, Need Authentication")]
     NeedAuthentication,
@@ -14,25 +18,99 @@ pub enum ClientError {
     InvalidHeaders(#[from] reqwest::header::InvalidHeaderValue),
     #[error("Invalid Multipart: {0}")]
     InvalidMultipart(String),
-    #[error("Send Request Error")]
+    #[error("Send Request Error: {0}")]
     ReqwestError(#[from] reqwest::Error),
-    #[error("Parse Error")]
-    ParseError,
+    /// the response of `endpoint` has a success status, but its body can't be parsed
+    #[error("Parse Error: unexpected response of {endpoint}: {body}")]
+    ParseError {
+        endpoint: String,
+        /// truncated to [`MAX_BODY_LEN`]
+        body: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// a status which is not documented for the endpoint
+    #[error("{method} {endpoint} responded with {status}: {body}")]
+    UnexpectedStatus {
+        method: Method,
+        endpoint: String,
+        status: StatusCode,
+        /// truncated to [`MAX_BODY_LEN`]
+        body: String,
+    },
 
     #[error("Torrents with hashes ({hash}) Not Found.")]
     TorrentNotFound { hash: String },
     #[error("Torrent File under {path} is invalid.")]
     TorrentFileInvalid { path: String },
+    /// a torrent file or reader added by [`crate::client::QbitApi::torrents_add_sources`]
+    /// can't be read
+    #[error("Failed to read torrent {path}: {source}")]
+    ReadTorrent {
+        /// the path, or the name of an in-memory source
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("File priority {priority} is invalid, or at least one file id is not an integer.")]
+    InvalidFilePriority { priority: u8 },
+    #[error(
+        "Metadata of torrent {hash} hasn't downloaded yet, or at least one file id was not found."
+    )]
+    TorrentMetadataNotReady { hash: String },
     #[error("Search Job {id} Not Found.")]
     SearchJobNotFound { id: u64 },
+    /// the plugins or category of a search are not installed or supported, see
+    /// [`crate::search::validate_start`]
+    #[error("Invalid Search: {0}")]
+    InvalidSearch(String),
     #[error("Bad Request: {0}")]
     BadRequest(String),
     #[error("Conflict: {0}")]
     Conflict(String),
     #[error("Error: {0}")]
     Other(String),
-    #[error("Unknown Error")]
-    Unknown,
+}
+
+impl ClientError {
+    /// The status code of the response which caused this error, if any
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::NeedAuthentication | Self::Authentication => Some(StatusCode::FORBIDDEN),
+            Self::TorrentNotFound { .. } | Self::SearchJobNotFound { .. } => {
+                Some(StatusCode::NOT_FOUND)
+            }
+            Self::TorrentFileInvalid { .. } => Some(StatusCode::UNSUPPORTED_MEDIA_TYPE),
+            Self::InvalidFilePriority { .. } | Self::BadRequest(_) => Some(StatusCode::BAD_REQUEST),
+            Self::TorrentMetadataNotReady { .. } | Self::Conflict(_) => Some(StatusCode::CONFLICT),
+            Self::UnexpectedStatus { status, .. } => Some(*status),
+            Self::ReqwestError(e) => e.status(),
+            _ => None,
+        }
+    }
+
+    /// Whether sending the same request again later may succeed
+    ///
+    /// timeouts, connection failures, `429 Too Many Requests` and `5xx` statuses are retryable.
+    /// everything else is fatal, the request or the state of qBittorrent has to change first
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::ReqwestError(e) if e.is_timeout() || e.is_connect() => true,
+            _ => self
+                .status()
+                .is_some_and(|s| s == StatusCode::TOO_MANY_REQUESTS || s.is_server_error()),
+        }
+    }
+}
+
+/// Truncate a response body to [`MAX_BODY_LEN`] bytes on a char boundary
+pub(crate) fn truncate_body(body: &str) -> String {
+    if body.len() <= MAX_BODY_LEN {
+        return body.to_owned();
+    }
+    let mut end = MAX_BODY_LEN;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...", &body[..end])
 }
 
 #[derive(Error, Debug)]
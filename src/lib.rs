@@ -1,6 +1,15 @@
 #![doc = include_str!("../README.md")]
 
 pub mod api;
+mod bencode;
 pub mod client;
+pub mod cluster;
 pub mod error;
+pub mod magnet;
+pub mod metainfo;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod search;
+pub mod selector;
+pub mod sync;
 pub mod types;
@@ -0,0 +1,376 @@
+//! Parse, normalize and build magnet links
+//!
+//! ```
+//! use qbit_api_rs::magnet::MagnetLink;
+//!
+//! let magnet: MagnetLink = "magnet:?xt=urn:btih:TZRYKYVLDQP45WO66FBIMTG5LJYBTYNK&dn=ubuntu"
+//!     .parse()
+//!     .unwrap();
+//! assert_eq!(magnet.hash(), "9e638562ab1c1fced9def142864cdd5a7019e1aa");
+//!
+//! let magnet = magnet.with_tracker("https://torrent.ubuntu.com/announce");
+//! assert_eq!(
+//!     magnet.to_string(),
+//!     "magnet:?xt=urn:btih:9e638562ab1c1fced9def142864cdd5a7019e1aa&dn=ubuntu\
+//!      &tr=https%3A%2F%2Ftorrent.ubuntu.com%2Fannounce"
+//! );
+//! ```
+
+use crate::error::TypesError;
+use crate::metainfo::Metainfo;
+use crate::types::torrents::InfoResponseItem;
+use std::fmt;
+use std::str::FromStr;
+
+/// multihash prefix of a sha2-256 digest, which `btmh` info hashes start with
+const SHA256_MULTIHASH: &str = "1220";
+
+/// A magnet link, with at least a v1 (`btih`) or v2 (`btmh`) info hash
+///
+/// info hashes are kept as lowercase hex, trackers and web seeds without duplicates,
+/// and parameters not known here are kept in order, so that it formats back to an
+/// equivalent link
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MagnetLink {
+    info_hash_v1: Option<String>,
+    info_hash_v2: Option<String>,
+    name: Option<String>,
+    length: Option<u64>,
+    trackers: Vec<String>,
+    web_seeds: Vec<String>,
+    extra: Vec<(String, String)>,
+}
+
+impl MagnetLink {
+    /// A magnet link of an info hash alone: a v1 hash as 40 hex or 32 base32 characters,
+    /// or a v2 hash as 64 hex characters
+    pub fn from_hash<H: AsRef<str>>(hash: H) -> Result<Self, TypesError> {
+        let hash = hash.as_ref();
+        let mut magnet = Self::empty();
+        match hash.len() {
+            64 if is_hex(hash) => magnet.info_hash_v2 = Some(hash.to_lowercase()),
+            _ => magnet.info_hash_v1 = Some(parse_btih(hash)?),
+        }
+        Ok(magnet)
+    }
+
+    fn empty() -> Self {
+        Self {
+            info_hash_v1: None,
+            info_hash_v2: None,
+            name: None,
+            length: None,
+            trackers: vec![],
+            web_seeds: vec![],
+            extra: vec![],
+        }
+    }
+
+    /// The hash identifying the torrent in the Web API, the v1 info hash, or the v2
+    /// info hash truncated to 20 bytes for v2-only torrents
+    pub fn hash(&self) -> &str {
+        match (&self.info_hash_v1, &self.info_hash_v2) {
+            (Some(v1), _) => v1,
+            (None, Some(v2)) => &v2[..40],
+            (None, None) => unreachable!("a magnet link has a v1 or v2 info hash"),
+        }
+    }
+
+    /// `btih`, lowercase hex
+    pub fn info_hash_v1(&self) -> Option<&str> {
+        self.info_hash_v1.as_deref()
+    }
+
+    /// `btmh` without its multihash prefix, the lowercase hex sha-256 digest
+    pub fn info_hash_v2(&self) -> Option<&str> {
+        self.info_hash_v2.as_deref()
+    }
+
+    /// `dn`
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// `xl`, in bytes
+    pub fn length(&self) -> Option<u64> {
+        self.length
+    }
+
+    /// `tr`
+    pub fn trackers(&self) -> &[String] {
+        &self.trackers
+    }
+
+    /// `ws`
+    pub fn web_seeds(&self) -> &[String] {
+        &self.web_seeds
+    }
+
+    /// other parameters, e.g. `x.pe` or `so`
+    pub fn extra(&self) -> &[(String, String)] {
+        &self.extra
+    }
+
+    pub fn with_name<N: AsRef<str>>(mut self, name: N) -> Self {
+        self.name = Some(name.as_ref().to_owned());
+        self
+    }
+
+    pub fn with_length(mut self, length: u64) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// Append a tracker, unless it's already there
+    pub fn with_tracker<U: AsRef<str>>(mut self, url: U) -> Self {
+        push_unique(&mut self.trackers, url.as_ref());
+        self
+    }
+
+    /// Append trackers, skipping those already there
+    pub fn with_trackers<I, U>(mut self, urls: I) -> Self
+    where
+        I: IntoIterator<Item = U>,
+        U: AsRef<str>,
+    {
+        for url in urls {
+            push_unique(&mut self.trackers, url.as_ref());
+        }
+        self
+    }
+
+    /// Append a web seed, unless it's already there
+    pub fn with_web_seed<U: AsRef<str>>(mut self, url: U) -> Self {
+        push_unique(&mut self.web_seeds, url.as_ref());
+        self
+    }
+
+    /// Whether both links are for the same torrent, comparing the info hashes they share
+    pub fn same_torrent(&self, other: &MagnetLink) -> bool {
+        let same =
+            |a: &Option<String>, b: &Option<String>| matches!((a, b), (Some(a), Some(b)) if a == b);
+        same(&self.info_hash_v1, &other.info_hash_v1)
+            || same(&self.info_hash_v2, &other.info_hash_v2)
+    }
+
+    /// Whether `torrent` of `torrents/info` is the torrent of this link
+    pub fn matches(&self, torrent: &InfoResponseItem) -> bool {
+        torrent.hash.eq_ignore_ascii_case(self.hash())
+            || matches!(&self.info_hash_v1, Some(v1) if torrent.infohash_v1.eq_ignore_ascii_case(v1))
+            || matches!(&self.info_hash_v2, Some(v2) if torrent.infohash_v2.eq_ignore_ascii_case(v2))
+    }
+}
+
+impl FromStr for MagnetLink {
+    type Err = TypesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |msg: &str| TypesError::Other(format!("invalid magnet link: {}", msg));
+        let url = url::Url::parse(s.trim()).map_err(|e| invalid(&e.to_string()))?;
+        if url.scheme() != "magnet" {
+            return Err(invalid("not a magnet uri"));
+        }
+
+        let mut magnet = Self::empty();
+        for (key, value) in url.query_pairs() {
+            let value = value.trim();
+            match key.as_ref() {
+                "xt" if value.starts_with("urn:btih:") => {
+                    magnet.info_hash_v1 = Some(parse_btih(&value["urn:btih:".len()..])?);
+                }
+                "xt" if value.starts_with("urn:btmh:") => {
+                    let digest = value["urn:btmh:".len()..]
+                        .strip_prefix(SHA256_MULTIHASH)
+                        .filter(|d| d.len() == 64 && is_hex(d))
+                        .ok_or_else(|| invalid("btmh is not a sha2-256 multihash"))?;
+                    magnet.info_hash_v2 = Some(digest.to_lowercase());
+                }
+                "dn" => magnet.name = Some(value.to_owned()),
+                "xl" => {
+                    magnet.length = Some(value.parse().map_err(|_| invalid("xl is not a length"))?)
+                }
+                "tr" => push_unique(&mut magnet.trackers, value),
+                "ws" => push_unique(&mut magnet.web_seeds, value),
+                _ => magnet.extra.push((key.into_owned(), value.to_owned())),
+            }
+        }
+        if magnet.info_hash_v1.is_none() && magnet.info_hash_v2.is_none() {
+            return Err(invalid("no btih nor btmh"));
+        }
+        Ok(magnet)
+    }
+}
+
+impl fmt::Display for MagnetLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut params = vec![];
+        if let Some(v1) = &self.info_hash_v1 {
+            params.push(format!("xt=urn:btih:{}", v1));
+        }
+        if let Some(v2) = &self.info_hash_v2 {
+            params.push(format!("xt=urn:btmh:{}{}", SHA256_MULTIHASH, v2));
+        }
+        if let Some(name) = &self.name {
+            params.push(format!("dn={}", encode(name)));
+        }
+        if let Some(length) = self.length {
+            params.push(format!("xl={}", length));
+        }
+        params.extend(self.trackers.iter().map(|t| format!("tr={}", encode(t))));
+        params.extend(self.web_seeds.iter().map(|w| format!("ws={}", encode(w))));
+        params.extend(
+            self.extra
+                .iter()
+                .map(|(k, v)| format!("{}={}", encode(k), encode(v))),
+        );
+        write!(f, "magnet:?{}", params.join("&"))
+    }
+}
+
+impl From<&Metainfo> for MagnetLink {
+    fn from(metainfo: &Metainfo) -> Self {
+        let mut magnet = Self {
+            info_hash_v1: metainfo.info_hash_v1().map(str::to_owned),
+            info_hash_v2: metainfo.info_hash_v2().map(str::to_owned),
+            name: Some(metainfo.name.clone()),
+            length: Some(metainfo.total_size()),
+            ..Self::empty()
+        };
+        for url in metainfo.announce.iter().flatten() {
+            push_unique(&mut magnet.trackers, url);
+        }
+        for url in metainfo.web_seeds.iter() {
+            push_unique(&mut magnet.web_seeds, url);
+        }
+        magnet
+    }
+}
+
+fn is_hex(s: &str) -> bool {
+    s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// a v1 info hash, hex or base32 encoded, as lowercase hex
+fn parse_btih(btih: &str) -> Result<String, TypesError> {
+    match btih.len() {
+        40 if is_hex(btih) => Some(btih.to_lowercase()),
+        32 => base32_to_hex(btih),
+        _ => None,
+    }
+    .ok_or_else(|| TypesError::Other(format!("invalid btih `{}`", btih)))
+}
+
+fn base32_to_hex(s: &str) -> Option<String> {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    let mut bits: u64 = 0;
+    let mut n_bits = 0;
+    let mut hex = String::new();
+    for c in s.to_ascii_uppercase().bytes() {
+        let v = ALPHABET.iter().position(|a| *a == c)? as u64;
+        bits = (bits << 5) | v;
+        n_bits += 5;
+        if n_bits >= 8 {
+            n_bits -= 8;
+            hex.push_str(&format!("{:02x}", (bits >> n_bits) & 0xff));
+        }
+    }
+    Some(hex)
+}
+
+fn push_unique(urls: &mut Vec<String>, url: &str) {
+    let url = url.trim();
+    if !url.is_empty() && !urls.iter().any(|u| u == url) {
+        urls.push(url.to_owned());
+    }
+}
+
+/// percent-encode all but the unreserved characters
+fn encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEX: &str = "9e638562ab1c1fced9def142864cdd5a7019e1aa";
+    const DIGEST: &str = "caf1e1c30e81cb361b9ee167c4aa64228a7fa4fa9f6105232b28ad099f3a302e";
+
+    #[test]
+    fn test_parse() {
+        let uri = "magnet:?xt=urn:btih:TZRYKYVLDQP45WO66FBIMTG5LJYBTYNK\
+                   &dn=ubuntu-22.04.2-live-server-amd64.iso&xl=1975971840\
+                   &tr=https%3A%2F%2Ftorrent.ubuntu.com%2Fannounce\
+                   &tr=https://torrent.ubuntu.com/announce&ws=https://example.com/&x.pe=1.2.3.4:5";
+        let m: MagnetLink = uri.parse().unwrap();
+        assert_eq!(m.hash(), HEX);
+        assert_eq!(m.info_hash_v2(), None);
+        assert_eq!(m.name(), Some("ubuntu-22.04.2-live-server-amd64.iso"));
+        assert_eq!(m.length(), Some(1975971840));
+        assert_eq!(m.trackers(), ["https://torrent.ubuntu.com/announce"]);
+        assert_eq!(m.web_seeds(), ["https://example.com/"]);
+        assert_eq!(m.extra(), [("x.pe".to_owned(), "1.2.3.4:5".to_owned())]);
+        assert_eq!(m.to_string().parse::<MagnetLink>().unwrap(), m);
+
+        let v2: MagnetLink = format!("magnet:?xt=urn:btmh:1220{}&dn=a%20b", DIGEST)
+            .parse()
+            .unwrap();
+        assert_eq!(v2.hash(), &DIGEST[..40]);
+        assert_eq!(v2.name(), Some("a b"));
+        assert_eq!(
+            v2.to_string(),
+            format!("magnet:?xt=urn:btmh:1220{}&dn=a%20b", DIGEST)
+        );
+
+        let hybrid: MagnetLink = format!("magnet:?xt=urn:btmh:1220{}&xt=urn:btih:{}", DIGEST, HEX)
+            .parse()
+            .unwrap();
+        assert_eq!(hybrid.hash(), HEX);
+        assert!(hybrid.same_torrent(&v2) && hybrid.same_torrent(&m));
+        assert!(!m.same_torrent(&v2));
+
+        for invalid in [
+            "https://example.com/a.torrent",
+            "magnet:?dn=no-hash",
+            "magnet:?xt=urn:btih:123",
+            "magnet:?xt=urn:btmh:1114abcd",
+            &format!("magnet:?xt=urn:btih:{}&xl=big", HEX),
+        ] {
+            assert!(invalid.parse::<MagnetLink>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_build() {
+        let m = MagnetLink::from_hash(HEX.to_uppercase())
+            .unwrap()
+            .with_name("ubuntu")
+            .with_trackers(["udp://a:1", "udp://b:2", "udp://a:1"])
+            .with_tracker(" ");
+        assert_eq!(
+            m.to_string(),
+            format!(
+                "magnet:?xt=urn:btih:{}&dn=ubuntu&tr=udp%3A%2F%2Fa%3A1&tr=udp%3A%2F%2Fb%3A2",
+                HEX
+            )
+        );
+        assert_eq!(
+            MagnetLink::from_hash(DIGEST).unwrap().info_hash_v2(),
+            Some(DIGEST)
+        );
+        assert!(MagnetLink::from_hash("not a hash").is_err());
+
+        let bytes = std::fs::read("./tests/ubuntu-22.04.2-live-server-amd64.iso.torrent").unwrap();
+        let m = MagnetLink::from(&Metainfo::from_bytes(&bytes).unwrap());
+        assert_eq!(m.hash(), HEX);
+        assert_eq!(m.length(), Some(1975971840));
+        assert_eq!(m.trackers().len(), 2);
+    }
+}
@@ -0,0 +1,398 @@
+//! Decode .torrent files to inspect them before adding
+//!
+//! ```no_run
+//! use qbit_api_rs::metainfo::Metainfo;
+//!
+//! # fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let bytes = std::fs::read("ubuntu-22.04.2-live-server-amd64.iso.torrent")?;
+//! let torrent = Metainfo::from_bytes(&bytes)?;
+//! println!("{} ({})", torrent.name, torrent.hash());
+//! for file in torrent.files.iter() {
+//!     println!("{}: {} bytes", file.path, file.length);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::bencode::{self, Value};
+use crate::error::TypesError;
+use crate::types::torrents::FilesResponseItem;
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+
+/// A decoded .torrent file, v1, v2 or hybrid
+///
+/// only built by [`Metainfo::from_bytes`], which ensures a v1 or v2 info hash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metainfo {
+    info_hash_v1: Option<String>,
+    info_hash_v2: Option<String>,
+    pub name: String,
+    pub piece_length: u64,
+    /// sha1 of each piece, empty for v2-only torrents
+    pub pieces: Vec<[u8; 20]>,
+    /// in order, without padding files. a single-file torrent has one file named `name`
+    pub files: Vec<MetainfoFile>,
+    /// tiers of tracker urls, from `announce-list`, or else a single tier of `announce`
+    pub announce: Vec<Vec<String>>,
+    /// `url-list`
+    pub web_seeds: Vec<String>,
+    pub private: bool,
+    /// unix timestamp
+    pub creation_date: Option<i64>,
+    pub created_by: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// A file of [`Metainfo`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetainfoFile {
+    /// joined with `/`, starting with the torrent name for multi-file torrents, like
+    /// [`FilesResponseItem::name`] of a torrent added with the original content layout
+    pub path: String,
+    pub length: u64,
+}
+
+impl Metainfo {
+    /// Decode and validate a .torrent file
+    pub fn from_bytes(torrent: &[u8]) -> Result<Self, TypesError> {
+        let root = bencode::decode(torrent)?;
+        let Some(info @ Value::Dict(_, raw_info)) = root.get("info") else {
+            return Err(invalid("no info dict"));
+        };
+        let v2 = matches!(info.get("meta version"), Some(Value::Int(2)));
+        let v1 = !v2 || info.get("pieces").is_some();
+
+        let name = text(info, "name").ok_or_else(|| invalid("no name"))?;
+        check_component(&name)?;
+        let piece_length = match info.get("piece length") {
+            Some(Value::Int(n)) if *n > 0 => *n as u64,
+            _ => return Err(invalid("no valid piece length")),
+        };
+
+        let pieces = match (v1, info.get("pieces")) {
+            (false, _) => vec![],
+            (true, Some(Value::Bytes(pieces))) if pieces.len() % 20 == 0 => pieces
+                .chunks_exact(20)
+                .map(|p| p.try_into().expect("chunks of 20 bytes"))
+                .collect(),
+            (true, _) => return Err(invalid("no valid pieces")),
+        };
+
+        let files = match v1 {
+            true => v1_files(info, &name)?,
+            false => {
+                let Some(tree @ Value::Dict(..)) = info.get("file tree") else {
+                    return Err(invalid("no file tree"));
+                };
+                let mut files = vec![];
+                v2_files(tree, "", &mut files)?;
+                // a single-file torrent is a tree of one file, named after the torrent
+                let single = files.len() == 1 && !files[0].path.contains('/');
+                for file in files.iter_mut() {
+                    file.path = match single {
+                        true => name.clone(),
+                        false => format!("{}/{}", name, file.path),
+                    };
+                }
+                files
+            }
+        };
+
+        let announce = match root.get("announce-list") {
+            Some(Value::List(tiers)) => tiers
+                .iter()
+                .map(|tier| match tier {
+                    Value::List(urls) => urls.iter().filter_map(as_text).collect::<Vec<_>>(),
+                    _ => vec![],
+                })
+                .filter(|tier| !tier.is_empty())
+                .collect(),
+            _ => text(&root, "announce")
+                .map(|a| vec![vec![a]])
+                .unwrap_or_default(),
+        };
+        let web_seeds = match root.get("url-list") {
+            Some(Value::List(urls)) => urls.iter().filter_map(as_text).collect(),
+            Some(url) => as_text(url).into_iter().filter(|u| !u.is_empty()).collect(),
+            None => vec![],
+        };
+
+        Ok(Self {
+            info_hash_v1: v1.then(|| bencode::to_hex(&Sha1::digest(raw_info))),
+            info_hash_v2: v2.then(|| bencode::to_hex(&Sha256::digest(raw_info))),
+            name,
+            piece_length,
+            pieces,
+            files,
+            announce,
+            web_seeds,
+            private: matches!(info.get("private"), Some(Value::Int(1))),
+            creation_date: match root.get("creation date") {
+                Some(Value::Int(date)) => Some(*date),
+                _ => None,
+            },
+            created_by: text(&root, "created by"),
+            comment: text(&root, "comment"),
+        })
+    }
+
+    /// The hash identifying the torrent in the Web API, the v1 info hash, or the v2
+    /// info hash truncated to 20 bytes for v2-only torrents
+    pub fn hash(&self) -> &str {
+        match (&self.info_hash_v1, &self.info_hash_v2) {
+            (Some(v1), _) => v1,
+            (None, Some(v2)) => &v2[..40],
+            (None, None) => unreachable!("a torrent has a v1 or v2 info hash"),
+        }
+    }
+
+    /// sha1 of the info dict, lowercase hex, `None` for v2-only torrents
+    pub fn info_hash_v1(&self) -> Option<&str> {
+        self.info_hash_v1.as_deref()
+    }
+
+    /// sha256 of the info dict, lowercase hex, `None` for v1-only torrents
+    pub fn info_hash_v2(&self) -> Option<&str> {
+        self.info_hash_v2.as_deref()
+    }
+
+    pub fn is_hybrid(&self) -> bool {
+        self.info_hash_v1.is_some() && self.info_hash_v2.is_some()
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|f| f.length).sum()
+    }
+
+    /// Whether `files` of `torrents/files` has the same paths and sizes, in any order
+    pub fn same_files(&self, files: &[FilesResponseItem]) -> bool {
+        let mut expected: Vec<(&str, u64)> = self
+            .files
+            .iter()
+            .map(|f| (f.path.as_str(), f.length))
+            .collect();
+        let mut actual: Vec<(&str, u64)> =
+            files.iter().map(|f| (f.name.as_str(), f.size)).collect();
+        expected.sort_unstable();
+        actual.sort_unstable();
+        expected == actual
+    }
+}
+
+/// The hash identifying a torrent file in the Web API, see [`Metainfo::hash`]
+///
+/// lighter than [`Metainfo::from_bytes`], only the info dict is required
+pub(crate) fn torrent_hash(torrent: &[u8]) -> Result<String, TypesError> {
+    let root = bencode::decode(torrent)?;
+    let Some(info @ Value::Dict(_, raw_info)) = root.get("info") else {
+        return Err(invalid("no info dict"));
+    };
+    let v2_only =
+        info.get("pieces").is_none() && matches!(info.get("meta version"), Some(Value::Int(2)));
+    match v2_only {
+        true => Ok(bencode::to_hex(&Sha256::digest(raw_info)[..20])),
+        false => Ok(bencode::to_hex(&Sha1::digest(raw_info))),
+    }
+}
+
+fn invalid(msg: &str) -> TypesError {
+    TypesError::Other(format!("invalid torrent: {}", msg))
+}
+
+fn as_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Bytes(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        _ => None,
+    }
+}
+
+/// the `{key}.utf-8` variant if any, as some clients store legacy encodings in `key`
+fn text(dict: &Value, key: &str) -> Option<String> {
+    dict.get(&format!("{}.utf-8", key))
+        .or_else(|| dict.get(key))
+        .and_then(as_text)
+}
+
+/// reject path components escaping the download directory
+fn check_component(component: &str) -> Result<(), TypesError> {
+    match component.is_empty() || component == "." || component == ".." || component.contains('/') {
+        true => Err(invalid(&format!("unsafe path component `{}`", component))),
+        false => Ok(()),
+    }
+}
+
+fn v1_files(info: &Value, name: &str) -> Result<Vec<MetainfoFile>, TypesError> {
+    let entries = match (info.get("length"), info.get("files")) {
+        (Some(Value::Int(length)), _) if *length >= 0 => {
+            return Ok(vec![MetainfoFile {
+                path: name.to_owned(),
+                length: *length as u64,
+            }])
+        }
+        (_, Some(Value::List(entries))) => entries,
+        _ => return Err(invalid("no length nor files")),
+    };
+
+    let mut files = vec![];
+    for entry in entries {
+        let padding = matches!(entry.get("attr"), Some(Value::Bytes(attr)) if attr.contains(&b'p'));
+        let length = match entry.get("length") {
+            Some(Value::Int(length)) if *length >= 0 => *length as u64,
+            _ => return Err(invalid("file without a valid length")),
+        };
+        let path = match entry.get("path.utf-8").or_else(|| entry.get("path")) {
+            Some(Value::List(components)) if !components.is_empty() => components
+                .iter()
+                .map(|c| as_text(c).ok_or_else(|| invalid("path component is not a string")))
+                .collect::<Result<Vec<_>, _>>()?,
+            _ => return Err(invalid("file without a path")),
+        };
+        for component in path.iter() {
+            check_component(component)?;
+        }
+        if !padding {
+            files.push(MetainfoFile {
+                path: format!("{}/{}", name, path.join("/")),
+                length,
+            });
+        }
+    }
+    Ok(files)
+}
+
+/// walk the v2 `file tree`, a file is a dict with an empty key holding its length.
+/// paths are relative to the root of the tree
+fn v2_files(tree: &Value, prefix: &str, files: &mut Vec<MetainfoFile>) -> Result<(), TypesError> {
+    let Value::Dict(entries, _) = tree else {
+        return Err(invalid("file tree node is not a dict"));
+    };
+    for (key, node) in entries.iter() {
+        if key.is_empty() {
+            let length = match node.get("length") {
+                Some(Value::Int(length)) if *length >= 0 => *length as u64,
+                _ => return Err(invalid("file without a valid length")),
+            };
+            files.push(MetainfoFile {
+                path: prefix.to_owned(),
+                length,
+            });
+            continue;
+        }
+        let component = String::from_utf8_lossy(key);
+        check_component(&component)?;
+        let path = match prefix.is_empty() {
+            true => component.into_owned(),
+            false => format!("{}/{}", prefix, component),
+        };
+        v2_files(node, &path, files)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PIECE_ROOT: &[u8; 32] = &[7; 32];
+
+    fn torrent(info: &[u8]) -> Vec<u8> {
+        [&b"d4:info"[..], info, b"e"].concat()
+    }
+
+    #[test]
+    fn test_v1_single_file() {
+        let bytes = std::fs::read("./tests/ubuntu-22.04.2-live-server-amd64.iso.torrent").unwrap();
+        let m = Metainfo::from_bytes(&bytes).unwrap();
+        // base32 `TZRYKYVLDQP45WO66FBIMTG5LJYBTYNK` of `examples/torrents.rs`
+        assert_eq!(m.hash(), "9e638562ab1c1fced9def142864cdd5a7019e1aa");
+        assert_eq!(m.info_hash_v2(), None);
+        assert_eq!(m.name, "ubuntu-22.04.2-live-server-amd64.iso");
+        assert_eq!(m.piece_length, 262144);
+        assert_eq!(m.pieces.len(), 7538);
+        assert_eq!(
+            m.files,
+            [MetainfoFile {
+                path: m.name.clone(),
+                length: 1975971840
+            }]
+        );
+        assert_eq!(
+            m.announce,
+            [
+                ["https://torrent.ubuntu.com/announce"],
+                ["https://ipv6.torrent.ubuntu.com/announce"]
+            ]
+        );
+        assert!(m.web_seeds.is_empty());
+        assert!(!m.private);
+        assert_eq!(m.creation_date, Some(1677174459));
+        assert_eq!(m.created_by.as_deref(), Some("mktorrent 1.1"));
+        assert_eq!(m.comment.as_deref(), Some("Ubuntu CD releases.ubuntu.com"));
+        assert_eq!(torrent_hash(&bytes).unwrap(), m.hash());
+    }
+
+    #[test]
+    fn test_v2_and_hybrid() {
+        let tree = [
+            &b"9:file treed1:bd0:d6:lengthi5e11:pieces root32:"[..],
+            PIECE_ROOT,
+            b"ee3:dird1:ad0:d6:lengthi3e11:pieces root32:",
+            PIECE_ROOT,
+            b"eeee",
+        ]
+        .concat();
+        let info = [
+            &b"d"[..],
+            &tree,
+            b"12:meta versioni2e4:name4:root12:piece lengthi16384e7:privatei1ee",
+        ]
+        .concat();
+        let bytes = [
+            &b"d4:info"[..],
+            &info,
+            b"8:url-list20:https://example.com/e",
+        ]
+        .concat();
+        let m = Metainfo::from_bytes(&bytes).unwrap();
+        let v2 = bencode::to_hex(&Sha256::digest(&info));
+        assert_eq!(m.info_hash_v1(), None);
+        assert_eq!(m.info_hash_v2(), Some(v2.as_str()));
+        assert_eq!(m.hash(), &v2[..40]);
+        assert_eq!(torrent_hash(&bytes).unwrap(), m.hash());
+        assert!(m.pieces.is_empty() && m.private);
+        assert_eq!(m.web_seeds, ["https://example.com/"]);
+        let paths: Vec<&str> = m.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["root/b", "root/dir/a"]);
+        assert_eq!(m.total_size(), 8);
+
+        let info = [
+            &b"d5:filesld6:lengthi3e4:pathl1:aeed4:attr1:p6:lengthi16381e4:pathl4:.pad5:16381eed6:lengthi5e4:pathl1:beee"[..],
+            &tree,
+            b"12:meta versioni2e4:name4:root12:piece lengthi16384e6:pieces40:",
+            &[1; 40],
+            b"e",
+        ]
+        .concat();
+        let m = Metainfo::from_bytes(&torrent(&info)).unwrap();
+        assert!(m.is_hybrid());
+        assert_eq!(m.hash(), bencode::to_hex(&Sha1::digest(&info)));
+        assert_eq!(m.pieces, [[1; 20], [1; 20]]);
+        let paths: Vec<&str> = m.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["root/a", "root/b"]);
+    }
+
+    #[test]
+    fn test_invalid() {
+        for info in [
+            &b"d6:lengthi1e12:piece lengthi16384e6:pieces20:aaaaaaaaaaaaaaaaaaaae"[..],
+            b"d6:lengthi1e4:name1:a12:piece lengthi16384e6:pieces3:aaae",
+            b"d4:name1:a12:piece lengthi16384e6:pieces0:e",
+            b"d5:filesld6:lengthi1e4:pathl2:..eee4:name1:a12:piece lengthi16384e6:pieces0:e",
+            b"d6:lengthi1e4:name2:..12:piece lengthi16384e6:pieces0:e",
+        ] {
+            assert!(Metainfo::from_bytes(&torrent(info)).is_err(), "{:?}", info);
+        }
+        assert!(Metainfo::from_bytes(b"d8:announce3:urle").is_err());
+    }
+}
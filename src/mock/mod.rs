@@ -0,0 +1,307 @@
+//! An in-memory fake of the qBittorrent Web API, enabled by the `mock` feature
+//!
+//! [`MockServer`] listens on a random local port and keeps its state in memory,
+//! so [`QbitClient`] can be driven end-to-end without a real qBittorrent instance
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), qbit_api_rs::error::ClientError> {
+//! use qbit_api_rs::client::QbitApi;
+//! use qbit_api_rs::mock::{MockServer, MockTorrent};
+//!
+//! let server = MockServer::start().await;
+//! server.add_torrent(MockTorrent::new("8c4adbf9ebe66f1d804fb6a4fb9b74966c3ab609", "debian"));
+//!
+//! let client = server.client();
+//! client.auth_login().await?;
+//! let torrents = client.torrents_info(&Default::default()).await?;
+//! assert_eq!(torrents.len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Supported endpoints:
+//! - `auth`: login (sets the `SID` cookie) and logout, every other endpoint answers `403` without a valid `SID`
+//! - `app`: version and webapiVersion
+//! - `torrents`: info, properties, files, add (magnet links and torrent files), delete, pause,
+//!   resume, rename, setCategory, categories, createCategory, editCategory, removeCategories,
+//!   addTags, removeTags, tags, createTags and deleteTags
+//! - `sync`: maindata, including partial updates computed against the requested `rid`
+//! - `transfer`: info, summing the speeds of the torrents
+//! - `search`: start, stop, status, results, delete and plugins
+//!
+//! Other endpoints answer `404`.
+
+mod routes;
+
+use crate::client::QbitClient;
+use crate::types::torrents::{FilesPriority, InfoState};
+use hyper::service::{make_service_fn, service_fn};
+use routes::State;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::oneshot;
+
+/// A fake qBittorrent Web API server, it stops when dropped
+///
+/// must be started inside a tokio runtime
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    username: String,
+    password: String,
+    state: Arc<Mutex<State>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    /// Start a server accepting the default credentials `admin` / `adminadmin`
+    pub async fn start() -> Self {
+        Self::start_with_credentials("admin", "adminadmin").await
+    }
+
+    /// Start a server accepting the given credentials
+    ///
+    /// # Panics
+    ///
+    /// if no local port can be bound
+    pub async fn start_with_credentials<U>(username: U, password: U) -> Self
+    where
+        U: AsRef<str>,
+    {
+        let username = username.as_ref().to_owned();
+        let password = password.as_ref().to_owned();
+        let state = Arc::new(Mutex::new(State::new(&username, &password)));
+
+        let listener =
+            std::net::TcpListener::bind("127.0.0.1:0").expect("failed to bind the mock server");
+        let addr = listener
+            .local_addr()
+            .expect("failed to get the address of the mock server");
+
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_conn| {
+            let state = service_state.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |req| routes::handle(state.clone(), req))) }
+        });
+        let (shutdown, rx) = oneshot::channel::<()>();
+        let server = hyper::Server::from_tcp(listener)
+            .expect("failed to start the mock server")
+            .serve(make_service)
+            .with_graceful_shutdown(async {
+                rx.await.ok();
+            });
+        tokio::spawn(async move {
+            if let Err(e) = server.await {
+                log::error!("mock server: {}", e);
+            }
+        });
+
+        Self {
+            addr,
+            username,
+            password,
+            state,
+            shutdown: Some(shutdown),
+        }
+    }
+
+    /// The host to connect to, e.g. `http://127.0.0.1:41234/`
+    pub fn uri(&self) -> String {
+        format!("http://{}/", self.addr)
+    }
+
+    /// A client pointing at this server with the accepted credentials, not logged in yet
+    pub fn client(&self) -> QbitClient {
+        QbitClient::new_with_user_pwd(self.uri(), self.username.clone(), self.password.clone())
+            .expect("the uri of the mock server is a valid host")
+    }
+
+    /// Add a torrent, replacing any torrent with the same hash
+    ///
+    /// its category and tags are created if they don't exist
+    pub fn add_torrent(&self, torrent: MockTorrent) {
+        self.state().insert_torrent(torrent);
+    }
+
+    /// Modify a torrent in place, returns `false` if there is no torrent with `hash`
+    pub fn update_torrent<F>(&self, hash: &str, f: F) -> bool
+    where
+        F: FnOnce(&mut MockTorrent),
+    {
+        match self.state().torrents.get_mut(&hash.to_lowercase()) {
+            Some(torrent) => {
+                f(torrent);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a torrent, returns it if it existed
+    pub fn remove_torrent(&self, hash: &str) -> Option<MockTorrent> {
+        self.state().torrents.remove(&hash.to_lowercase())
+    }
+
+    /// A snapshot of a torrent
+    pub fn torrent(&self, hash: &str) -> Option<MockTorrent> {
+        self.state().torrents.get(&hash.to_lowercase()).cloned()
+    }
+
+    /// A snapshot of all torrents, ordered by hash
+    pub fn torrents(&self) -> Vec<MockTorrent> {
+        self.state().torrents.values().cloned().collect()
+    }
+
+    /// Add a category, replacing the save path if it exists
+    pub fn add_category<C, P>(&self, category: C, save_path: P)
+    where
+        C: AsRef<str>,
+        P: AsRef<str>,
+    {
+        self.state()
+            .categories
+            .insert(category.as_ref().to_owned(), save_path.as_ref().to_owned());
+    }
+
+    /// Add a tag
+    pub fn add_tag<T>(&self, tag: T)
+    where
+        T: AsRef<str>,
+    {
+        self.state().tags.insert(tag.as_ref().to_owned());
+    }
+
+    /// Add a result returned by search jobs whose pattern is contained in its `file_name`
+    pub fn add_search_result(&self, result: MockSearchResult) {
+        self.state().search_results.push(result);
+    }
+
+    /// Let new search jobs report `Running` for this many polls of `search/status` or
+    /// `search/results` before they stop by themselves, `1` by default
+    pub fn set_search_polls(&self, polls: u32) {
+        self.state().search_polls = polls;
+    }
+
+    /// Whether the search job is running, `None` if it doesn't exist
+    pub fn search_running(&self, id: u64) -> Option<bool> {
+        self.state().search_jobs.get(&id).map(|j| j.running)
+    }
+
+    /// Invalidate all `SID` cookies, as if they expired
+    pub fn expire_sessions(&self) {
+        self.state().sessions.clear();
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+/// A torrent held by [`MockServer`]
+///
+/// fields reported by the Web API but not kept here are derived from these,
+/// e.g. `amount_left` from `size` and `progress`
+#[derive(Debug, Clone)]
+pub struct MockTorrent {
+    pub hash: String,
+    pub name: String,
+    pub size: i64,
+    /// from `0.0` to `1.0`
+    pub progress: f64,
+    pub state: InfoState,
+    /// empty for no category
+    pub category: String,
+    pub tags: Vec<String>,
+    pub save_path: String,
+    pub added_on: u64,
+    pub dlspeed: i64,
+    pub upspeed: i64,
+    pub ratio: f64,
+    /// in seconds
+    pub seeding_time: i64,
+    /// url of the current tracker, empty for none
+    pub tracker: String,
+    pub files: Vec<MockFile>,
+}
+
+impl MockTorrent {
+    /// A stalled torrent with nothing downloaded, added now
+    pub fn new<H, N>(hash: H, name: N) -> Self
+    where
+        H: AsRef<str>,
+        N: AsRef<str>,
+    {
+        Self {
+            hash: hash.as_ref().to_lowercase(),
+            name: name.as_ref().to_owned(),
+            size: 0,
+            progress: 0.0,
+            state: InfoState::StalledDL,
+            category: String::new(),
+            tags: vec![],
+            save_path: "/downloads/".to_owned(),
+            added_on: now(),
+            dlspeed: 0,
+            upspeed: 0,
+            ratio: 0.0,
+            seeding_time: 0,
+            tracker: String::new(),
+            files: vec![],
+        }
+    }
+
+    /// Append a file, the size of the torrent grows accordingly
+    pub fn file<N>(mut self, name: N, size: u64) -> Self
+    where
+        N: AsRef<str>,
+    {
+        self.size += size as i64;
+        self.files.push(MockFile {
+            name: name.as_ref().to_owned(),
+            size,
+            progress: self.progress,
+            priority: FilesPriority::Normal,
+        });
+        self
+    }
+}
+
+/// A file of [`MockTorrent`]
+#[derive(Debug, Clone)]
+pub struct MockFile {
+    pub name: String,
+    pub size: u64,
+    pub progress: f64,
+    pub priority: FilesPriority,
+}
+
+/// A search result returned by search jobs of [`MockServer`]
+#[derive(Debug, Clone, Default)]
+pub struct MockSearchResult {
+    pub file_name: String,
+    pub file_url: String,
+    pub file_size: f64,
+    pub nb_seeders: u64,
+    pub nb_leechers: u64,
+    pub site_url: String,
+    pub descr_link: String,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
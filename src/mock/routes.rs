@@ -0,0 +1,981 @@
+use super::{MockSearchResult, MockTorrent};
+use crate::magnet::MagnetLink;
+use crate::metainfo::Metainfo;
+use crate::types::torrents::InfoState;
+use hyper::header::{CONTENT_TYPE, COOKIE, SET_COOKIE};
+use hyper::{Body, Request, Response, StatusCode};
+use serde_json::{json, Map, Value};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+/// how many past `rid`s a partial `sync/maindata` update can be computed against
+const MAX_SNAPSHOTS: usize = 32;
+/// qBittorrent refuses to start more running search jobs than this
+const MAX_RUNNING_SEARCHES: usize = 5;
+
+type Params = HashMap<String, String>;
+
+#[derive(Debug)]
+pub(super) struct State {
+    username: String,
+    password: String,
+    pub(super) sessions: HashSet<String>,
+    next_session: u64,
+    pub(super) torrents: BTreeMap<String, MockTorrent>,
+    /// name -> save path
+    pub(super) categories: BTreeMap<String, String>,
+    pub(super) tags: BTreeSet<String>,
+    rid: u64,
+    snapshots: VecDeque<(u64, Snapshot)>,
+    pub(super) search_jobs: BTreeMap<u64, SearchJob>,
+    next_search_id: u64,
+    pub(super) search_results: Vec<MockSearchResult>,
+    /// how many polls a new search job reports `Running`
+    pub(super) search_polls: u32,
+}
+
+/// what `sync/maindata` reported for a `rid`
+#[derive(Debug, Clone)]
+struct Snapshot {
+    torrents: BTreeMap<String, Map<String, Value>>,
+    categories: BTreeMap<String, String>,
+    tags: BTreeSet<String>,
+}
+
+#[derive(Debug)]
+pub(super) struct SearchJob {
+    pattern: String,
+    pub(super) running: bool,
+    /// polls left until the job stops by itself
+    polls: u32,
+}
+
+struct Reply {
+    status: StatusCode,
+    body: String,
+    json: bool,
+}
+
+impl Reply {
+    fn ok() -> Self {
+        Self::text(StatusCode::OK, "")
+    }
+
+    fn text(status: StatusCode, body: &str) -> Self {
+        Self {
+            status,
+            body: body.to_owned(),
+            json: false,
+        }
+    }
+
+    fn json(value: Value) -> Self {
+        Self {
+            status: StatusCode::OK,
+            body: value.to_string(),
+            json: true,
+        }
+    }
+
+    fn status(status: StatusCode) -> Self {
+        let reason = status.canonical_reason().unwrap_or_default();
+        Self::text(status, reason)
+    }
+}
+
+pub(super) async fn handle(
+    state: Arc<Mutex<State>>,
+    req: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let (parts, body) = req.into_parts();
+    let body = hyper::body::to_bytes(body).await.unwrap_or_default();
+
+    let mut params: Params = parts
+        .uri
+        .query()
+        .map(|q| {
+            url::form_urlencoded::parse(q.as_bytes())
+                .into_owned()
+                .collect()
+        })
+        .unwrap_or_default();
+    let content_type = parts
+        .headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let mut files = vec![];
+    if content_type.starts_with("multipart/form-data") {
+        for part in parse_multipart(content_type, &body) {
+            match part.filename {
+                Some(_) => files.push(part.data),
+                None => {
+                    let value = String::from_utf8_lossy(&part.data).into_owned();
+                    params.insert(part.name, value);
+                }
+            }
+        }
+    } else {
+        params.extend(url::form_urlencoded::parse(&body).into_owned());
+    }
+    let sid = parts
+        .headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .find_map(|c| c.trim().strip_prefix("SID="))
+        .map(|s| s.to_owned());
+
+    let mut state = state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let path = parts.uri.path();
+    log::debug!("mock server: {} {}", parts.method, path);
+
+    let mut builder = Response::builder();
+    let reply = match path.strip_prefix("/api/v2/") {
+        Some("auth/login") => match state.login(&params) {
+            Some(sid) => {
+                builder = builder.header(SET_COOKIE, format!("SID={}; HttpOnly; path=/", sid));
+                Reply::text(StatusCode::OK, "Ok.")
+            }
+            None => Reply::text(StatusCode::OK, "Fails."),
+        },
+        Some(_) if !sid.as_ref().is_some_and(|s| state.sessions.contains(s)) => {
+            Reply::status(StatusCode::FORBIDDEN)
+        }
+        Some("auth/logout") => {
+            if let Some(sid) = &sid {
+                state.sessions.remove(sid);
+            }
+            Reply::ok()
+        }
+        Some(route) => state.route(route, &params, files),
+        None => Reply::status(StatusCode::NOT_FOUND),
+    };
+
+    if reply.json {
+        builder = builder.header(CONTENT_TYPE, "application/json");
+    } else {
+        builder = builder.header(CONTENT_TYPE, "text/plain; charset=UTF-8");
+    }
+    let resp = builder
+        .status(reply.status)
+        .body(Body::from(reply.body))
+        .unwrap_or_default();
+    Ok(resp)
+}
+
+impl State {
+    pub(super) fn new(username: &str, password: &str) -> Self {
+        Self {
+            username: username.to_owned(),
+            password: password.to_owned(),
+            sessions: HashSet::new(),
+            next_session: 1,
+            torrents: BTreeMap::new(),
+            categories: BTreeMap::new(),
+            tags: BTreeSet::new(),
+            rid: 0,
+            snapshots: VecDeque::new(),
+            search_jobs: BTreeMap::new(),
+            next_search_id: 1,
+            search_results: vec![],
+            search_polls: 1,
+        }
+    }
+
+    pub(super) fn insert_torrent(&mut self, torrent: MockTorrent) {
+        if !torrent.category.is_empty() {
+            self.categories.entry(torrent.category.clone()).or_default();
+        }
+        self.tags.extend(torrent.tags.iter().cloned());
+        self.torrents.insert(torrent.hash.clone(), torrent);
+    }
+
+    fn login(&mut self, params: &Params) -> Option<String> {
+        let username = params.get("username")?;
+        let password = params.get("password")?;
+        if *username != self.username || *password != self.password {
+            return None;
+        }
+        let sid = format!("{:032x}", self.next_session);
+        self.next_session += 1;
+        self.sessions.insert(sid.clone());
+        Some(sid)
+    }
+
+    fn route(&mut self, route: &str, params: &Params, files: Vec<Vec<u8>>) -> Reply {
+        match route {
+            "app/version" => Reply::text(StatusCode::OK, "v4.6.2"),
+            "app/webapiVersion" => Reply::text(StatusCode::OK, "2.9.3"),
+            "torrents/info" => self.torrents_info(params),
+            "torrents/properties" => self.torrents_properties(params),
+            "torrents/files" => self.torrents_files(params),
+            "torrents/add" => self.torrents_add(params, files),
+            "torrents/delete" => {
+                for hash in self.select(params) {
+                    self.torrents.remove(&hash);
+                }
+                Reply::ok()
+            }
+            "torrents/pause" => {
+                for hash in self.select(params) {
+                    if let Some(t) = self.torrents.get_mut(&hash) {
+                        t.state = match t.progress < 1.0 {
+                            true => InfoState::PausedDL,
+                            false => InfoState::PausedUP,
+                        };
+                    }
+                }
+                Reply::ok()
+            }
+            "torrents/resume" => {
+                for hash in self.select(params) {
+                    if let Some(t) = self.torrents.get_mut(&hash) {
+                        t.state = match t.progress < 1.0 {
+                            true => InfoState::StalledDL,
+                            false => InfoState::StalledUP,
+                        };
+                    }
+                }
+                Reply::ok()
+            }
+            "torrents/rename" => self.torrents_rename(params),
+            "torrents/setCategory" => self.torrents_set_category(params),
+            "torrents/categories" => self.torrents_categories(),
+            "torrents/createCategory" => self.torrents_create_category(params),
+            "torrents/editCategory" => self.torrents_edit_category(params),
+            "torrents/removeCategories" => {
+                let categories = param(params, "categories");
+                for category in categories.split('\n') {
+                    self.categories.remove(category);
+                    for t in self.torrents.values_mut() {
+                        if t.category == category {
+                            t.category.clear();
+                        }
+                    }
+                }
+                Reply::ok()
+            }
+            "torrents/addTags" => {
+                let tags = split_tags(params);
+                self.tags.extend(tags.iter().cloned());
+                for hash in self.select(params) {
+                    if let Some(t) = self.torrents.get_mut(&hash) {
+                        for tag in tags.iter() {
+                            if !t.tags.contains(tag) {
+                                t.tags.push(tag.clone());
+                            }
+                        }
+                    }
+                }
+                Reply::ok()
+            }
+            "torrents/removeTags" => {
+                let tags = split_tags(params);
+                for hash in self.select(params) {
+                    if let Some(t) = self.torrents.get_mut(&hash) {
+                        // no tags means removing all of them
+                        t.tags.retain(|tag| !tags.is_empty() && !tags.contains(tag));
+                    }
+                }
+                Reply::ok()
+            }
+            "torrents/tags" => Reply::json(json!(self.tags)),
+            "torrents/createTags" => {
+                self.tags.extend(split_tags(params));
+                Reply::ok()
+            }
+            "torrents/deleteTags" => {
+                let tags = split_tags(params);
+                for tag in tags.iter() {
+                    self.tags.remove(tag);
+                }
+                for t in self.torrents.values_mut() {
+                    t.tags.retain(|tag| !tags.contains(tag));
+                }
+                Reply::ok()
+            }
+            "sync/maindata" => self.sync_maindata(params),
+            "transfer/info" => Reply::json(json!({
+                "connection_status": "connected",
+                "dl_info_speed": self.torrents.values().map(|t| t.dlspeed).sum::<i64>(),
+                "dl_info_data": 0,
+                "up_info_speed": self.torrents.values().map(|t| t.upspeed).sum::<i64>(),
+                "up_info_data": 0,
+                "dl_rate_limit": 0,
+                "up_rate_limit": 0,
+                "dht_nodes": 0,
+            })),
+            "search/start" => self.search_start(params),
+            "search/stop" => match self.search_job(params) {
+                Some(job) => {
+                    job.running = false;
+                    Reply::ok()
+                }
+                None => Reply::status(StatusCode::NOT_FOUND),
+            },
+            "search/status" => self.search_status(params),
+            "search/results" => self.search_results(params),
+            "search/delete" => {
+                let id = param(params, "id").parse().unwrap_or_default();
+                match self.search_jobs.remove(&id) {
+                    Some(_) => Reply::ok(),
+                    None => Reply::status(StatusCode::NOT_FOUND),
+                }
+            }
+            "search/plugins" => Reply::json(json!([{
+                "enabled": true,
+                "fullName": "Mock",
+                "name": "mock",
+                "supportedCategories": [{"id": "all", "name": "All categories"}],
+                "url": "http://mock.invalid",
+                "version": "1.0"
+            }])),
+            _ => Reply::status(StatusCode::NOT_FOUND),
+        }
+    }
+
+    /// hashes selected by the `hashes` parameter, `all` selects every torrent
+    fn select(&self, params: &Params) -> Vec<String> {
+        let hashes = param(params, "hashes");
+        if hashes == "all" {
+            return self.torrents.keys().cloned().collect();
+        }
+        hashes
+            .split('|')
+            .map(|h| h.to_lowercase())
+            .filter(|h| self.torrents.contains_key(h))
+            .collect()
+    }
+
+    fn torrents_info(&self, params: &Params) -> Reply {
+        // like qBittorrent, an empty `hashes` is no filter
+        let hashes: Option<Vec<String>> = params
+            .get("hashes")
+            .filter(|h| !h.is_empty())
+            .map(|h| h.split('|').map(|h| h.to_lowercase()).collect());
+        let filter = param(params, "filter");
+        let mut items: Vec<Map<String, Value>> = self
+            .torrents
+            .values()
+            .filter(|t| hashes.iter().all(|h| h.contains(&t.hash)))
+            .filter(|t| params.get("category").iter().all(|c| t.category == **c))
+            .filter(|t| params.get("tag").iter().all(|tag| t.tags.contains(tag)))
+            .filter(|t| matches_filter(t, filter))
+            .map(torrent_json)
+            .collect();
+
+        if let Some(sort) = params.get("sort") {
+            items.sort_by(|a, b| compare_values(a.get(sort), b.get(sort)));
+        }
+        if param(params, "reverse") == "true" {
+            items.reverse();
+        }
+        let len = items.len() as i64;
+        let offset = param(params, "offset").parse::<i64>().unwrap_or_default();
+        // a negative offset counts from the end
+        let offset = match offset < 0 {
+            true => (len + offset).max(0),
+            false => offset.min(len),
+        } as usize;
+        let limit = param(params, "limit")
+            .parse::<usize>()
+            .ok()
+            .filter(|l| *l > 0)
+            .unwrap_or(usize::MAX);
+        let items: Vec<_> = items.into_iter().skip(offset).take(limit).collect();
+
+        Reply::json(json!(items))
+    }
+
+    fn torrents_properties(&self, params: &Params) -> Reply {
+        let Some(t) = self.torrents.get(&param(params, "hash").to_lowercase()) else {
+            return Reply::status(StatusCode::NOT_FOUND);
+        };
+        let completed = completed(t);
+        Reply::json(json!({
+            "save_path": t.save_path,
+            "creation_date": t.added_on,
+            "piece_size": 16384,
+            "comment": "",
+            "total_wasted": 0,
+            "total_uploaded": 0,
+            "total_uploaded_session": 0,
+            "total_downloaded": completed,
+            "total_downloaded_session": completed,
+            "up_limit": -1,
+            "dl_limit": -1,
+            "time_elapsed": 0,
+            "seeding_time": 0,
+            "nb_connections": 0,
+            "nb_connections_limit": 100,
+            "share_ratio": 0.0,
+            "addition_date": t.added_on,
+            "completion_date": -1,
+            "created_by": "",
+            "dl_speed_avg": 0,
+            "dl_speed": t.dlspeed,
+            "eta": 8640000,
+            "last_seen": -1,
+            "peers": 0,
+            "peers_total": 0,
+            "pieces_have": completed / 16384,
+            "pieces_num": (t.size + 16383) / 16384,
+            "reannounce": 0,
+            "seeds": 0,
+            "seeds_total": 0,
+            "total_size": t.size,
+            "up_speed_avg": 0,
+            "up_speed": t.upspeed,
+        }))
+    }
+
+    fn torrents_files(&self, params: &Params) -> Reply {
+        let Some(t) = self.torrents.get(&param(params, "hash").to_lowercase()) else {
+            return Reply::status(StatusCode::NOT_FOUND);
+        };
+        let indexes: Option<Vec<usize>> = params
+            .get("indexes")
+            .map(|i| i.split('|').filter_map(|i| i.parse().ok()).collect());
+        let files: Vec<Value> = t
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| indexes.iter().all(|i| i.contains(index)))
+            .map(|(index, f)| {
+                json!({
+                    "index": index,
+                    "name": f.name,
+                    "size": f.size,
+                    "progress": f.progress,
+                    "priority": f.priority,
+                    "is_seed": f.progress >= 1.0,
+                    "piece_range": [0, 0],
+                    "availability": 0.0,
+                })
+            })
+            .collect();
+        Reply::json(json!(files))
+    }
+
+    /// magnet links and torrent files are understood, other urls are ignored. an invalid
+    /// torrent file rejects the whole request, like qBittorrent does
+    fn torrents_add(&mut self, params: &Params, files: Vec<Vec<u8>>) -> Reply {
+        let mut torrents = vec![];
+        for file in files.iter() {
+            let Ok(metainfo) = Metainfo::from_bytes(file) else {
+                return Reply::status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+            };
+            let mut torrent = MockTorrent::new(metainfo.hash(), &metainfo.name);
+            for file in metainfo.files.iter() {
+                torrent = torrent.file(&file.path, file.length);
+            }
+            torrents.push(torrent);
+        }
+        for url in param(params, "urls").split('\n') {
+            if let Ok(magnet) = url.parse::<MagnetLink>() {
+                let name = magnet.name().unwrap_or(magnet.hash());
+                torrents.push(MockTorrent::new(magnet.hash(), name));
+            }
+        }
+
+        let added = !torrents.is_empty();
+        for mut torrent in torrents {
+            if let Some(name) = params.get("rename") {
+                torrent.name = name.clone();
+            }
+            if let Some(category) = params.get("category") {
+                torrent.category = category.clone();
+            }
+            if let Some(tags) = params.get("tags") {
+                torrent.tags = tags.split(',').map(|t| t.trim().to_owned()).collect();
+            }
+            if let Some(savepath) = params.get("savepath") {
+                torrent.save_path = savepath.clone();
+            }
+            if param(params, "paused") == "true" || param(params, "stopped") == "true" {
+                torrent.state = InfoState::PausedDL;
+            }
+            // adding an existing torrent is ignored
+            if !self.torrents.contains_key(&torrent.hash) {
+                self.insert_torrent(torrent);
+            }
+        }
+        match added {
+            true => Reply::text(StatusCode::OK, "Ok."),
+            false => Reply::text(StatusCode::OK, "Fails."),
+        }
+    }
+
+    fn torrents_rename(&mut self, params: &Params) -> Reply {
+        let name = param(params, "name");
+        if name.is_empty() {
+            return Reply::status(StatusCode::CONFLICT);
+        }
+        match self.torrents.get_mut(&param(params, "hash").to_lowercase()) {
+            Some(t) => {
+                t.name = name.to_owned();
+                Reply::ok()
+            }
+            None => Reply::status(StatusCode::NOT_FOUND),
+        }
+    }
+
+    fn torrents_set_category(&mut self, params: &Params) -> Reply {
+        let category = param(params, "category");
+        if !category.is_empty() && !self.categories.contains_key(category) {
+            return Reply::status(StatusCode::CONFLICT);
+        }
+        for hash in self.select(params) {
+            if let Some(t) = self.torrents.get_mut(&hash) {
+                t.category = category.to_owned();
+            }
+        }
+        Reply::ok()
+    }
+
+    fn torrents_categories(&self) -> Reply {
+        Reply::json(categories_json(&self.categories))
+    }
+
+    fn torrents_create_category(&mut self, params: &Params) -> Reply {
+        let category = param(params, "category");
+        if category.is_empty() {
+            return Reply::status(StatusCode::BAD_REQUEST);
+        }
+        if self.categories.contains_key(category) {
+            return Reply::status(StatusCode::CONFLICT);
+        }
+        self.categories
+            .insert(category.to_owned(), param(params, "savePath").to_owned());
+        Reply::ok()
+    }
+
+    fn torrents_edit_category(&mut self, params: &Params) -> Reply {
+        let category = param(params, "category");
+        if category.is_empty() {
+            return Reply::status(StatusCode::BAD_REQUEST);
+        }
+        match self.categories.get_mut(category) {
+            Some(save_path) => {
+                *save_path = param(params, "savePath").to_owned();
+                Reply::ok()
+            }
+            None => Reply::status(StatusCode::CONFLICT),
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            torrents: self
+                .torrents
+                .iter()
+                .map(|(hash, t)| {
+                    let mut item = torrent_json(t);
+                    // the hash is the key in sync/maindata
+                    item.remove("hash");
+                    (hash.clone(), item)
+                })
+                .collect(),
+            categories: self.categories.clone(),
+            tags: self.tags.clone(),
+        }
+    }
+
+    fn sync_maindata(&mut self, params: &Params) -> Reply {
+        let current = self.snapshot();
+        let rid = param(params, "rid").parse::<u64>().unwrap_or_default();
+        let previous = self
+            .snapshots
+            .iter()
+            .find(|(r, _)| rid != 0 && *r == rid)
+            .map(|(_, s)| s);
+
+        let mut resp = match previous {
+            Some(previous) => diff(previous, &current),
+            None => {
+                let mut resp = Map::new();
+                resp.insert("full_update".into(), json!(true));
+                resp.insert("torrents".into(), json!(current.torrents));
+                resp.insert("categories".into(), categories_json(&current.categories));
+                resp.insert("tags".into(), json!(current.tags));
+                resp.insert(
+                    "server_state".into(),
+                    json!({
+                        "connection_status": "connected",
+                        "dl_info_speed": self.torrents.values().map(|t| t.dlspeed).sum::<i64>(),
+                        "up_info_speed": self.torrents.values().map(|t| t.upspeed).sum::<i64>(),
+                        "queueing": false,
+                        "refresh_interval": 1500,
+                    }),
+                );
+                resp
+            }
+        };
+
+        self.rid += 1;
+        self.snapshots.push_back((self.rid, current));
+        if self.snapshots.len() > MAX_SNAPSHOTS {
+            self.snapshots.pop_front();
+        }
+        resp.insert("rid".into(), json!(self.rid));
+        Reply::json(Value::Object(resp))
+    }
+
+    fn search_job(&mut self, params: &Params) -> Option<&mut SearchJob> {
+        let id = param(params, "id").parse::<u64>().ok()?;
+        self.search_jobs.get_mut(&id)
+    }
+
+    fn search_start(&mut self, params: &Params) -> Reply {
+        let running = self.search_jobs.values().filter(|j| j.running).count();
+        if running >= MAX_RUNNING_SEARCHES {
+            return Reply::status(StatusCode::CONFLICT);
+        }
+        let id = self.next_search_id;
+        self.next_search_id += 1;
+        let job = SearchJob {
+            pattern: param(params, "pattern").to_lowercase(),
+            running: self.search_polls > 0,
+            polls: self.search_polls,
+        };
+        self.search_jobs.insert(id, job);
+        Reply::json(json!({ "id": id }))
+    }
+
+    /// the results of a job, which reports `Running` for `search_polls` polls
+    fn search_poll(&mut self, id: u64) -> Option<(&'static str, Vec<&MockSearchResult>)> {
+        let job = self.search_jobs.get_mut(&id)?;
+        let status = match job.running {
+            true => "Running",
+            false => "Stopped",
+        };
+        job.polls = job.polls.saturating_sub(1);
+        job.running = job.running && job.polls > 0;
+        let pattern = job.pattern.clone();
+        let results = self
+            .search_results
+            .iter()
+            .filter(|r| r.file_name.to_lowercase().contains(&pattern))
+            .collect();
+        Some((status, results))
+    }
+
+    fn search_status(&mut self, params: &Params) -> Reply {
+        let ids: Vec<u64> = match params.get("id") {
+            Some(id) => match id.parse::<u64>() {
+                Ok(id) if self.search_jobs.contains_key(&id) => vec![id],
+                _ => return Reply::status(StatusCode::NOT_FOUND),
+            },
+            None => self.search_jobs.keys().copied().collect(),
+        };
+        let mut items = vec![];
+        for id in ids {
+            if let Some((status, results)) = self.search_poll(id) {
+                items.push(json!({"id": id, "status": status, "total": results.len()}));
+            }
+        }
+        Reply::json(json!(items))
+    }
+
+    fn search_results(&mut self, params: &Params) -> Reply {
+        let id = param(params, "id").parse::<u64>().unwrap_or_default();
+        let limit = param(params, "limit").parse::<i64>().unwrap_or_default();
+        let offset = param(params, "offset").parse::<i64>().unwrap_or_default();
+        let Some((status, results)) = self.search_poll(id) else {
+            return Reply::status(StatusCode::NOT_FOUND);
+        };
+        let total = results.len() as i64;
+        // a negative offset counts from the end
+        let start = match offset < 0 {
+            true => total + offset,
+            false => offset,
+        };
+        if start < 0 || start > total {
+            return Reply::status(StatusCode::CONFLICT);
+        }
+        let take = match limit > 0 {
+            true => limit as usize,
+            false => usize::MAX,
+        };
+        let results: Vec<Value> = results
+            .into_iter()
+            .skip(start as usize)
+            .take(take)
+            .map(|r| {
+                json!({
+                    "descrLink": r.descr_link,
+                    "fileName": r.file_name,
+                    "fileSize": r.file_size,
+                    "fileUrl": r.file_url,
+                    "nbLeechers": r.nb_leechers,
+                    "nbSeeders": r.nb_seeders,
+                    "siteUrl": r.site_url,
+                })
+            })
+            .collect();
+        Reply::json(json!({"results": results, "status": status, "total": total}))
+    }
+}
+
+fn param<'a>(params: &'a Params, key: &str) -> &'a str {
+    params.get(key).map(|v| v.as_str()).unwrap_or_default()
+}
+
+fn split_tags(params: &Params) -> Vec<String> {
+    param(params, "tags")
+        .split(',')
+        .map(|t| t.trim().to_owned())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn completed(t: &MockTorrent) -> i64 {
+    (t.size as f64 * t.progress.clamp(0.0, 1.0)) as i64
+}
+
+fn is_paused(t: &MockTorrent) -> bool {
+    matches!(t.state, InfoState::PausedDL | InfoState::PausedUP)
+}
+
+/// an approximation of the `filter` of `torrents/info`
+fn matches_filter(t: &MockTorrent, filter: &str) -> bool {
+    let active = t.dlspeed > 0 || t.upspeed > 0;
+    match filter {
+        "downloading" => t.progress < 1.0 && !is_paused(t),
+        "seeding" => t.progress >= 1.0 && !is_paused(t),
+        "completed" => t.progress >= 1.0,
+        "paused" => is_paused(t),
+        "resumed" => !is_paused(t),
+        "active" => active,
+        "inactive" => !active,
+        "stalled" => matches!(t.state, InfoState::StalledDL | InfoState::StalledUP),
+        "stalled_uploading" => t.state == InfoState::StalledUP,
+        "stalled_downloading" => t.state == InfoState::StalledDL,
+        "errored" => matches!(t.state, InfoState::Error | InfoState::MissingFiles),
+        _ => true,
+    }
+}
+
+fn compare_values(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    match (a, b) {
+        (Some(Value::Number(a)), Some(Value::Number(b))) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Some(Value::String(a)), Some(Value::String(b))) => a.cmp(b),
+        (Some(Value::Bool(a)), Some(Value::Bool(b))) => a.cmp(b),
+        _ => Ordering::Equal,
+    }
+}
+
+/// a torrent as in `torrents/info`
+fn torrent_json(t: &MockTorrent) -> Map<String, Value> {
+    let completed = completed(t);
+    let value = json!({
+        "added_on": t.added_on,
+        "amount_left": t.size - completed,
+        "auto_tmm": false,
+        "category": t.category,
+        "completed": completed,
+        "completion_on": 0,
+        "dl_limit": -1,
+        "dlspeed": t.dlspeed,
+        "downloaded": completed,
+        "downloaded_session": completed,
+        "eta": 8640000,
+        "f_l_piece_prio": false,
+        "force_start": false,
+        "hash": t.hash,
+        "infohash_v1": t.hash,
+        "infohash_v2": "",
+        "last_activity": t.added_on,
+        "magnet_uri": magnet_uri(t),
+        "max_ratio": -1,
+        "max_seeding_time": -1,
+        "name": t.name,
+    });
+    // split in two, `json!` of all fields exceeds the default recursion limit
+    let rest = json!({
+        "num_complete": 0,
+        "num_incomplete": 0,
+        "num_leechs": 0,
+        "num_seeds": 0,
+        "priority": 0,
+        "progress": t.progress,
+        "ratio": t.ratio,
+        "ratio_limit": -2,
+        "save_path": t.save_path,
+        "seeding_time": t.seeding_time,
+        "seeding_time_limit": -2,
+        "seen_complete": -1,
+        "seq_dl": false,
+        "size": t.size,
+        "state": t.state,
+        "super_seeding": false,
+        "tags": t.tags.join(", "),
+        "time_active": 0,
+        "total_size": t.size,
+        "tracker": t.tracker,
+        "up_limit": -1,
+        "uploaded": 0,
+        "uploaded_session": 0,
+        "upspeed": t.upspeed,
+    });
+    let mut map = Map::new();
+    for value in [value, rest] {
+        if let Value::Object(fields) = value {
+            map.extend(fields);
+        }
+    }
+    map
+}
+
+fn categories_json(categories: &BTreeMap<String, String>) -> Value {
+    let categories: Map<String, Value> = categories
+        .iter()
+        .map(|(name, save_path)| (name.clone(), json!({"name": name, "savePath": save_path})))
+        .collect();
+    Value::Object(categories)
+}
+
+/// a partial `sync/maindata` update, only changed fields of torrents are included
+fn diff(previous: &Snapshot, current: &Snapshot) -> Map<String, Value> {
+    let mut resp = Map::new();
+
+    let mut torrents = Map::new();
+    for (hash, item) in current.torrents.iter() {
+        match previous.torrents.get(hash) {
+            Some(old) => {
+                let changed: Map<String, Value> = item
+                    .iter()
+                    .filter(|(k, v)| old.get(*k) != Some(*v))
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect();
+                if !changed.is_empty() {
+                    torrents.insert(hash.clone(), Value::Object(changed));
+                }
+            }
+            None => {
+                torrents.insert(hash.clone(), Value::Object(item.clone()));
+            }
+        }
+    }
+    let torrents_removed: Vec<&String> = previous
+        .torrents
+        .keys()
+        .filter(|h| !current.torrents.contains_key(*h))
+        .collect();
+
+    let categories: BTreeMap<String, String> = current
+        .categories
+        .iter()
+        .filter(|(name, path)| previous.categories.get(*name) != Some(*path))
+        .map(|(name, path)| (name.clone(), path.clone()))
+        .collect();
+    let categories_removed: Vec<&String> = previous
+        .categories
+        .keys()
+        .filter(|c| !current.categories.contains_key(*c))
+        .collect();
+
+    let tags: Vec<&String> = current.tags.difference(&previous.tags).collect();
+    let tags_removed: Vec<&String> = previous.tags.difference(&current.tags).collect();
+
+    if !torrents.is_empty() {
+        resp.insert("torrents".into(), Value::Object(torrents));
+    }
+    if !torrents_removed.is_empty() {
+        resp.insert("torrents_removed".into(), json!(torrents_removed));
+    }
+    if !categories.is_empty() {
+        resp.insert("categories".into(), categories_json(&categories));
+    }
+    if !categories_removed.is_empty() {
+        resp.insert("categories_removed".into(), json!(categories_removed));
+    }
+    if !tags.is_empty() {
+        resp.insert("tags".into(), json!(tags));
+    }
+    if !tags_removed.is_empty() {
+        resp.insert("tags_removed".into(), json!(tags_removed));
+    }
+    resp
+}
+
+fn magnet_uri(t: &MockTorrent) -> String {
+    match MagnetLink::from_hash(&t.hash) {
+        Ok(magnet) => magnet.with_name(&t.name).to_string(),
+        Err(_) => format!("magnet:?xt=urn:btih:{}", t.hash),
+    }
+}
+
+struct Part {
+    name: String,
+    filename: Option<String>,
+    data: Vec<u8>,
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// a minimal `multipart/form-data` parser, good enough for what reqwest sends
+fn parse_multipart(content_type: &str, body: &[u8]) -> Vec<Part> {
+    let Some(boundary) = content_type
+        .split(';')
+        .find_map(|p| p.trim().strip_prefix("boundary="))
+    else {
+        return vec![];
+    };
+    let delimiter = format!("--{}", boundary.trim_matches('"'));
+    let delimiter = delimiter.as_bytes();
+
+    let mut parts = vec![];
+    let mut rest = match find(body, delimiter) {
+        Some(i) => &body[i + delimiter.len()..],
+        None => return parts,
+    };
+    while let Some(end) = find(rest, delimiter) {
+        let section = &rest[..end];
+        rest = &rest[end + delimiter.len()..];
+
+        let section = section.strip_prefix(b"\r\n").unwrap_or(section);
+        let section = section.strip_suffix(b"\r\n").unwrap_or(section);
+        let Some(split) = find(section, b"\r\n\r\n") else {
+            continue;
+        };
+        let headers = String::from_utf8_lossy(&section[..split]);
+        let mut name = None;
+        let mut filename = None;
+        for line in headers.lines() {
+            let Some(disposition) = line
+                .split_once(':')
+                .filter(|(k, _)| k.trim().eq_ignore_ascii_case("content-disposition"))
+                .map(|(_, v)| v)
+            else {
+                continue;
+            };
+            for attr in disposition.split(';') {
+                match attr.trim().split_once('=') {
+                    Some(("name", v)) => name = Some(v.trim_matches('"').to_owned()),
+                    Some(("filename", v)) => filename = Some(v.trim_matches('"').to_owned()),
+                    _ => {}
+                }
+            }
+        }
+        if let Some(name) = name {
+            parts.push(Part {
+                name,
+                filename,
+                data: section[split + 4..].to_vec(),
+            });
+        }
+    }
+    parts
+}
@@ -0,0 +1,555 @@
+//! Follow a search job until it completes, and pick from its results
+//!
+//! [`QbitApi::search_start`] returns a [`SearchJob`], which streams the results as they
+//! arrive and stops the job when dropped. a [`SearchAggregator`] merges the same release
+//! found by several plugins and ranks the results, and [`add_result_to_client`] adds the
+//! picked one.
+//!
+//! ```no_run
+//! use futures_util::StreamExt;
+//! use qbit_api_rs::client::{QbitApi, QbitClient};
+//!
+//! # async fn run(client: QbitClient) -> Result<(), qbit_api_rs::error::ClientError> {
+//! let mut job = client
+//!     .search_start("ubuntu", "all", "all")
+//!     .await?
+//!     .delete_on_drop(true);
+//!
+//! let mut results = job.results();
+//! while let Some(result) = results.next().await {
+//!     let result = result?;
+//!     println!("{} ({} seeders)", result.file_name, result.nb_seeders);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::{QbitApi, QbitClient};
+use crate::error::ClientError;
+use crate::magnet::MagnetLink;
+use crate::types::search::{
+    Plugin, PluginSelection, ResultsResponseItem, SearchCategory, SearchStatus,
+};
+use crate::types::torrents::AddMultipart;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// used unless [`SearchJob::with_poll_interval`] is called
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Check the parameters of `search/start` against the installed plugins
+///
+/// named plugins must be installed and enabled, and the category must be supported
+/// by at least one selected plugin. qBittorrent doesn't reject them, a typo just
+/// leads to a search without any result
+pub fn validate_start(
+    installed: &[Plugin],
+    plugins: &PluginSelection,
+    category: &SearchCategory,
+) -> Result<(), ClientError> {
+    let selected: Vec<&Plugin> = match plugins {
+        PluginSelection::All => installed.iter().collect(),
+        PluginSelection::Enabled => installed.iter().filter(|p| p.enabled).collect(),
+        PluginSelection::Named(names) => {
+            let mut selected = vec![];
+            for name in names {
+                match installed.iter().find(|p| p.name == *name) {
+                    Some(p) if p.enabled => selected.push(p),
+                    Some(_) => {
+                        return Err(ClientError::InvalidSearch(format!(
+                            "plugin {} is disabled",
+                            name
+                        )))
+                    }
+                    None => {
+                        return Err(ClientError::InvalidSearch(format!(
+                            "plugin {} is not installed",
+                            name
+                        )))
+                    }
+                }
+            }
+            selected
+        }
+    };
+    if selected.is_empty() {
+        return Err(ClientError::InvalidSearch(format!(
+            "no plugin is selected by {}",
+            plugins
+        )));
+    }
+
+    let supported = *category == SearchCategory::All
+        || selected
+            .iter()
+            .flat_map(|p| p.supported_categories.iter())
+            .any(|c| c.id == category.id());
+    if !supported {
+        return Err(ClientError::InvalidSearch(format!(
+            "category {} is not supported by {}",
+            category.id(),
+            plugins
+        )));
+    }
+
+    Ok(())
+}
+
+/// A search job, see the [module docs](self)
+///
+/// when dropped in a tokio runtime, a job which may still be running is stopped,
+/// and deleted if [`SearchJob::delete_on_drop`] is set, by a spawned task
+#[derive(Debug)]
+pub struct SearchJob<A = QbitClient>
+where
+    A: QbitApi + Clone + 'static,
+{
+    api: A,
+    id: u64,
+    /// results already yielded
+    offset: i64,
+    poll_interval: Duration,
+    /// the server reported the job stopped, or it was stopped by [`SearchJob::stop`]
+    stopped: bool,
+    delete_on_drop: bool,
+    /// the job was cancelled or deleted, nothing is left to do on drop
+    finished: bool,
+}
+
+impl<A> SearchJob<A>
+where
+    A: QbitApi + Clone + 'static,
+{
+    /// Follow the existing search job `id`
+    pub fn new(api: A, id: u64) -> Self {
+        Self {
+            api,
+            id,
+            offset: 0,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            stopped: false,
+            delete_on_drop: false,
+            finished: false,
+        }
+    }
+
+    /// Wait this long between polls of [`SearchJob::results`], 1 second by default
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Delete the job when the handle is dropped or cancelled, instead of just stopping it
+    pub fn delete_on_drop(mut self, delete: bool) -> Self {
+        self.delete_on_drop = delete;
+        self
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn api(&self) -> &A {
+        &self.api
+    }
+
+    /// # `/api/v2/search/status`
+    pub async fn status(&self) -> Result<SearchStatus, ClientError> {
+        let status = self.api.search_status(Some(self.id)).await?;
+        status
+            .into_iter()
+            .find(|s| s.id == self.id)
+            .map(|s| s.status)
+            .ok_or(ClientError::SearchJobNotFound { id: self.id })
+    }
+
+    /// # `/api/v2/search/results`
+    ///
+    /// Poll the job and yield the results it hasn't yielded yet, until it's stopped
+    ///
+    /// the status is polled before the results, so once it's `Stopped` the results
+    /// fetched after it are complete. the stream ends after an error, and calling
+    /// `results` again continues after the last result yielded.
+    pub fn results(&mut self) -> BoxStream<'_, Result<ResultsResponseItem, ClientError>> {
+        let init = (self, VecDeque::new(), false, true);
+        stream::unfold(init, |(job, mut pending, mut done, mut first)| async move {
+            loop {
+                if let Some(result) = pending.pop_front() {
+                    return Some((Ok(result), (job, pending, done, first)));
+                }
+                if done {
+                    return None;
+                }
+                if !first {
+                    tokio::time::sleep(job.poll_interval).await;
+                }
+                first = false;
+
+                let status = match job.status().await {
+                    Ok(status) => status,
+                    Err(e) => return Some((Err(e), (job, pending, true, first))),
+                };
+                let resp = match job.api.search_results(job.id, None, Some(job.offset)).await {
+                    Ok(resp) => resp,
+                    Err(e) => return Some((Err(e), (job, pending, true, first))),
+                };
+                job.offset += resp.results.len() as i64;
+                pending.extend(resp.results);
+                if status == SearchStatus::Stopped {
+                    job.stopped = true;
+                    done = true;
+                }
+            }
+        })
+        .boxed()
+    }
+
+    /// # `/api/v2/search/stop`
+    ///
+    /// the results found so far are kept, and can still be streamed
+    pub async fn stop(&mut self) -> Result<(), ClientError> {
+        self.api.search_stop(self.id).await?;
+        self.stopped = true;
+
+        Ok(())
+    }
+
+    /// Stop the job, and delete it if [`SearchJob::delete_on_drop`] is set
+    pub async fn cancel(mut self) -> Result<(), ClientError> {
+        self.finished = true;
+        if self.delete_on_drop {
+            return self.api.search_delete(self.id).await;
+        }
+        if !self.stopped {
+            self.api.search_stop(self.id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// # `/api/v2/search/delete`
+    ///
+    /// delete the job and its results, stopping it if it's running
+    pub async fn delete(mut self) -> Result<(), ClientError> {
+        self.finished = true;
+        self.api.search_delete(self.id).await
+    }
+}
+
+impl<A> Drop for SearchJob<A>
+where
+    A: QbitApi + Clone + 'static,
+{
+    fn drop(&mut self) {
+        if self.finished || (self.stopped && !self.delete_on_drop) {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            log::warn!("search job {} dropped outside of a tokio runtime", self.id);
+            return;
+        };
+        let api = self.api.clone();
+        let id = self.id;
+        let delete = self.delete_on_drop;
+        handle.spawn(async move {
+            let res = match delete {
+                true => api.search_delete(id).await,
+                false => api.search_stop(id).await,
+            };
+            if let Err(e) = res {
+                log::warn!("failed to clean up search job {}: {}", id, e);
+            }
+        });
+    }
+}
+
+/// A release found by one or more plugins, see [`SearchAggregator`]
+#[derive(Debug, Clone)]
+pub struct AggregatedResult {
+    /// lowercase hex info hash, if any of the results is a magnet link
+    pub info_hash: Option<String>,
+    /// the first result found, with the highest seeders and leechers of all of them,
+    /// and a magnet link as `file_url` if any of them has one
+    pub result: ResultsResponseItem,
+    /// every result merged into this one, in the order they were found
+    pub sources: Vec<ResultsResponseItem>,
+    /// by [`SearchAggregator::with_scorer`], higher is better
+    pub score: f64,
+}
+
+/// The default score of [`SearchAggregator`], seeders first and then leechers
+pub fn default_score(result: &AggregatedResult) -> f64 {
+    result.result.nb_seeders as f64 + result.result.nb_leechers as f64 / 10.0
+}
+
+/// Merge the results found by several plugins and rank them
+///
+/// results with the same magnet info hash are merged, or else results with the same
+/// size and the same name ignoring case and punctuation. the swarm of a release is the
+/// same on every site, so the seeders and leechers of merged results are the highest
+/// reported rather than their sum.
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use qbit_api_rs::client::{QbitApi, QbitClient};
+/// use qbit_api_rs::search::{add_result_to_client, SearchAggregator};
+///
+/// # async fn run(client: QbitClient) -> Result<(), qbit_api_rs::error::ClientError> {
+/// let mut job = client.search_start("ubuntu", "all", "all").await?;
+/// let mut aggregator = SearchAggregator::new();
+/// let mut results = job.results();
+/// while let Some(result) = results.next().await {
+///     aggregator.push(result?);
+/// }
+///
+/// if let Some(best) = aggregator.ranked().first() {
+///     add_result_to_client(&client, &best.result, Default::default()).await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchAggregator {
+    results: Vec<AggregatedResult>,
+    by_hash: HashMap<String, usize>,
+    by_name_size: HashMap<(String, u64), usize>,
+    scorer: fn(&AggregatedResult) -> f64,
+}
+
+impl Default for SearchAggregator {
+    fn default() -> Self {
+        Self {
+            results: vec![],
+            by_hash: HashMap::new(),
+            by_name_size: HashMap::new(),
+            scorer: default_score,
+        }
+    }
+}
+
+impl SearchAggregator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rank with `scorer` instead of [`default_score`]
+    pub fn with_scorer(mut self, scorer: fn(&AggregatedResult) -> f64) -> Self {
+        self.scorer = scorer;
+        for result in self.results.iter_mut() {
+            result.score = scorer(result);
+        }
+        self
+    }
+
+    /// Add a result, merging it into the same release found before
+    pub fn push(&mut self, result: ResultsResponseItem) {
+        let info_hash = result
+            .file_url
+            .parse::<MagnetLink>()
+            .ok()
+            .map(|m| m.hash().to_owned());
+        let name_size = (normalize_name(&result.file_name), result.file_size as u64);
+
+        let found = match &info_hash {
+            // a result without a hash may be the same release, but not one with another hash
+            Some(hash) => self.by_hash.get(hash).copied().or_else(|| {
+                self.by_name_size
+                    .get(&name_size)
+                    .copied()
+                    .filter(|i| self.results[*i].info_hash.is_none())
+            }),
+            None => self.by_name_size.get(&name_size).copied(),
+        };
+        let i = match found {
+            Some(i) => {
+                let merged = &mut self.results[i];
+                merged.result.nb_seeders = merged.result.nb_seeders.max(result.nb_seeders);
+                merged.result.nb_leechers = merged.result.nb_leechers.max(result.nb_leechers);
+                if merged.info_hash.is_none() && info_hash.is_some() {
+                    merged.info_hash = info_hash.clone();
+                    merged.result.file_url = result.file_url.clone();
+                }
+                merged.sources.push(result);
+                i
+            }
+            None => {
+                self.results.push(AggregatedResult {
+                    info_hash: info_hash.clone(),
+                    result: result.clone(),
+                    sources: vec![result],
+                    score: 0.0,
+                });
+                self.results.len() - 1
+            }
+        };
+
+        if let Some(hash) = info_hash {
+            self.by_hash.entry(hash).or_insert(i);
+        }
+        self.by_name_size.entry(name_size).or_insert(i);
+        let merged = &mut self.results[i];
+        merged.score = (self.scorer)(merged);
+    }
+
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// The merged results, best first, the order they were found breaks ties
+    pub fn ranked(&self) -> Vec<&AggregatedResult> {
+        let mut ranked: Vec<&AggregatedResult> = self.results.iter().collect();
+        ranked.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+        ranked
+    }
+
+    /// Like [`SearchAggregator::ranked`], consuming the aggregator
+    pub fn into_ranked(self) -> Vec<AggregatedResult> {
+        let mut ranked = self.results;
+        ranked.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+        ranked
+    }
+}
+
+impl Extend<ResultsResponseItem> for SearchAggregator {
+    fn extend<I: IntoIterator<Item = ResultsResponseItem>>(&mut self, iter: I) {
+        for result in iter {
+            self.push(result);
+        }
+    }
+}
+
+impl FromIterator<ResultsResponseItem> for SearchAggregator {
+    fn from_iter<I: IntoIterator<Item = ResultsResponseItem>>(iter: I) -> Self {
+        let mut aggregator = Self::new();
+        aggregator.extend(iter);
+        aggregator
+    }
+}
+
+/// # `/api/v2/torrents/add`
+///
+/// Add the torrent of a search result, `options` apply but their `urls` and `torrents`
+/// are replaced by the `file_url` of the result
+pub async fn add_result_to_client<A>(
+    api: &A,
+    result: &ResultsResponseItem,
+    options: AddMultipart,
+) -> Result<(), ClientError>
+where
+    A: QbitApi,
+{
+    let ta = AddMultipart {
+        urls: vec![result.file_url.clone()],
+        torrents: vec![],
+        ..options
+    };
+    api.torrents_add(ta).await
+}
+
+/// lowercase alphanumeric words separated by a space, so `Ubuntu.22.04-Server`
+/// and `ubuntu 22 04 server` are the same
+fn normalize_name(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::search::PluginsResponse;
+
+    fn installed() -> PluginsResponse {
+        let s = r#"[
+            {"enabled": true, "fullName": "Legit Torrents", "name": "legittorrents",
+             "supportedCategories": [{"id": "all", "name": "All categories"}, {"id": "tv", "name": "TV shows"}],
+             "url": "http://www.legittorrents.info", "version": "2.3"},
+            {"enabled": false, "fullName": "Old", "name": "old",
+             "supportedCategories": ["all", "movies"],
+             "url": "http://example.com", "version": "1.0"}
+        ]"#;
+        serde_json::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_validate_start() {
+        let installed = installed();
+        let ok = |p: &str, c: &str| validate_start(&installed, &p.into(), &c.into()).is_ok();
+
+        assert!(ok("all", "all"));
+        assert!(ok("all", "movies"));
+        assert!(ok("enabled", "tv"));
+        assert!(ok("legittorrents", "tv"));
+        // the only plugin supporting movies is disabled
+        assert!(!ok("enabled", "movies"));
+        assert!(!ok("old", "all"));
+        assert!(!ok("legittorents", "all"));
+        assert!(!ok("legittorrents", "tvshows"));
+        assert!(validate_start(&[], &PluginSelection::All, &SearchCategory::All).is_err());
+    }
+
+    fn result(name: &str, url: &str, size: f64, seeders: u64) -> ResultsResponseItem {
+        ResultsResponseItem {
+            descr_link: String::new(),
+            file_name: name.to_owned(),
+            file_size: size,
+            file_url: url.to_owned(),
+            nb_leechers: 0,
+            nb_seeders: seeders,
+            site_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let hex = "magnet:?xt=urn:btih:7e2fc0391f2d855affed3b0545927bddd5189bc6&dn=a";
+        let base32 = "magnet:?xt=urn:btih:PYX4AOI7FWCVV77NHMCULET33XKRRG6G";
+        let aggregator: SearchAggregator = [
+            result(
+                "Ubuntu 22.04 Server",
+                "https://a.example/1.torrent",
+                100.0,
+                5,
+            ),
+            result("ubuntu.22.04-server", hex, 100.0, 20),
+            result("Other name", base32, 1.0, 8),
+            result(
+                "Ubuntu 22.04 Server",
+                "https://b.example/2.torrent",
+                200.0,
+                50,
+            ),
+            result("Debian", "https://c.example/3.torrent", 300.0, 1),
+        ]
+        .into_iter()
+        .collect();
+
+        let ranked = aggregator.ranked();
+        assert_eq!(ranked.len(), 3);
+        // another size is another release
+        assert_eq!(ranked[0].result.file_size, 200.0);
+        // merged by name and size, then by the base32 hash
+        assert_eq!(ranked[1].sources.len(), 3);
+        assert_eq!(ranked[1].result.nb_seeders, 20);
+        assert_eq!(ranked[1].result.file_url, hex);
+        assert_eq!(
+            ranked[1].info_hash.as_deref(),
+            Some("7e2fc0391f2d855affed3b0545927bddd5189bc6")
+        );
+        assert_eq!(ranked[2].result.file_name, "Debian");
+
+        let ranked = aggregator
+            .with_scorer(|r| -(r.result.file_size))
+            .into_ranked();
+        assert_eq!(ranked[0].result.file_name, "Ubuntu 22.04 Server");
+        assert_eq!(ranked[0].result.file_size, 100.0);
+    }
+}
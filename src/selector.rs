@@ -0,0 +1,410 @@
+//! Select torrents by conditions `torrents/info` can't express
+//!
+//! a [`TorrentSelector`] combines conditions with `&`, `|` and `!`. the conditions on the top
+//! level `&` which `torrents/info` supports are sent in the [`InfoQuery`], the rest are
+//! evaluated locally over the returned torrents.
+//!
+//! ```no_run
+//! use qbit_api_rs::client::{QbitApi, QbitClient};
+//! use qbit_api_rs::selector::{Cmp, TorrentSelector as S};
+//! use std::time::Duration;
+//!
+//! # async fn run(client: QbitClient) -> Result<(), qbit_api_rs::error::ClientError> {
+//! let selector = S::category_in(["tv", "movies"])
+//!     & S::ratio(Cmp::Gt, 2.0)
+//!     & S::seeding_time(Cmp::Gt, Duration::from_secs(7 * 24 * 3600))
+//!     & S::tracker_contains("foo")
+//!     & !S::tag("keep");
+//!
+//! let torrents = client.torrents_select(&selector).await?;
+//! println!("deleting {} torrents", torrents.len());
+//! client.torrents_delete(&selector, true).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::types::torrents::{InfoFilter, InfoQuery, InfoResponseItem, InfoState};
+use std::ops::{BitAnd, BitOr, Not};
+use std::time::Duration;
+
+/// A comparison of a numeric field with a value, `field <cmp> value`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cmp {
+    Lt,
+    Le,
+    Eq,
+    Ge,
+    Gt,
+}
+
+impl Cmp {
+    fn eval<T: PartialOrd>(self, field: T, value: T) -> bool {
+        match self {
+            Cmp::Lt => field < value,
+            Cmp::Le => field <= value,
+            Cmp::Eq => field == value,
+            Cmp::Ge => field >= value,
+            Cmp::Gt => field > value,
+        }
+    }
+}
+
+/// A condition on torrents, see the [module docs](self)
+#[derive(Debug, Clone)]
+pub enum TorrentSelector {
+    /// every torrent
+    All,
+    /// hash is one of these
+    Hashes(Vec<String>),
+    /// category is one of these, the empty category is uncategorized
+    Category(Vec<String>),
+    /// has this tag
+    Tag(String),
+    /// matches the `filter` of `torrents/info`
+    Filter(InfoFilter),
+    State(InfoState),
+    /// name contains this, ignoring case
+    NameContains(String),
+    /// url of the current tracker contains this, ignoring case
+    TrackerContains(String),
+    SavePathStartsWith(String),
+    Ratio(Cmp, f64),
+    /// from `0.0` to `1.0`
+    Progress(Cmp, f64),
+    /// in bytes
+    Size(Cmp, i64),
+    SeedingTime(Cmp, Duration),
+    /// time since the torrent was added
+    Age(Cmp, Duration),
+    And(Vec<TorrentSelector>),
+    Or(Vec<TorrentSelector>),
+    Not(Box<TorrentSelector>),
+}
+
+impl TorrentSelector {
+    pub fn hashes<I, H>(hashes: I) -> Self
+    where
+        I: IntoIterator<Item = H>,
+        H: AsRef<str>,
+    {
+        Self::Hashes(
+            hashes
+                .into_iter()
+                .map(|h| h.as_ref().to_lowercase())
+                .collect(),
+        )
+    }
+
+    pub fn category<C: AsRef<str>>(category: C) -> Self {
+        Self::category_in([category])
+    }
+
+    pub fn category_in<I, C>(categories: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: AsRef<str>,
+    {
+        Self::Category(
+            categories
+                .into_iter()
+                .map(|c| c.as_ref().to_owned())
+                .collect(),
+        )
+    }
+
+    pub fn tag<T: AsRef<str>>(tag: T) -> Self {
+        Self::Tag(tag.as_ref().to_owned())
+    }
+
+    pub fn filter(filter: InfoFilter) -> Self {
+        Self::Filter(filter)
+    }
+
+    pub fn state(state: InfoState) -> Self {
+        Self::State(state)
+    }
+
+    pub fn name_contains<N: AsRef<str>>(name: N) -> Self {
+        Self::NameContains(name.as_ref().to_owned())
+    }
+
+    pub fn tracker_contains<T: AsRef<str>>(tracker: T) -> Self {
+        Self::TrackerContains(tracker.as_ref().to_owned())
+    }
+
+    pub fn save_path_starts_with<P: AsRef<str>>(path: P) -> Self {
+        Self::SavePathStartsWith(path.as_ref().to_owned())
+    }
+
+    pub fn ratio(cmp: Cmp, ratio: f64) -> Self {
+        Self::Ratio(cmp, ratio)
+    }
+
+    pub fn progress(cmp: Cmp, progress: f64) -> Self {
+        Self::Progress(cmp, progress)
+    }
+
+    pub fn size(cmp: Cmp, size: i64) -> Self {
+        Self::Size(cmp, size)
+    }
+
+    pub fn seeding_time(cmp: Cmp, seeding_time: Duration) -> Self {
+        Self::SeedingTime(cmp, seeding_time)
+    }
+
+    pub fn age(cmp: Cmp, age: Duration) -> Self {
+        Self::Age(cmp, age)
+    }
+
+    /// both `self` and `other`, same as `self & other`
+    pub fn and(self, other: Self) -> Self {
+        match self {
+            Self::All => other,
+            Self::And(mut selectors) => {
+                selectors.push(other);
+                Self::And(selectors)
+            }
+            selector => Self::And(vec![selector, other]),
+        }
+    }
+
+    /// either `self` or `other`, same as `self | other`
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Self::Or(mut selectors) => {
+                selectors.push(other);
+                Self::Or(selectors)
+            }
+            selector => Self::Or(vec![selector, other]),
+        }
+    }
+
+    /// Whether the torrent matches, evaluating every condition locally
+    pub fn matches(&self, torrent: &InfoResponseItem) -> bool {
+        match self {
+            Self::All => true,
+            Self::Hashes(hashes) => hashes.iter().any(|h| h.eq_ignore_ascii_case(&torrent.hash)),
+            Self::Category(categories) => categories.contains(&torrent.category),
+            Self::Tag(tag) => torrent.tags.split(',').any(|t| t.trim() == tag),
+            Self::Filter(filter) => filter_matches(filter, torrent),
+            Self::State(state) => torrent.state == *state,
+            Self::NameContains(name) => contains_ignore_case(&torrent.name, name),
+            Self::TrackerContains(tracker) => contains_ignore_case(&torrent.tracker, tracker),
+            Self::SavePathStartsWith(path) => torrent.save_path.starts_with(path.as_str()),
+            Self::Ratio(cmp, ratio) => cmp.eval(torrent.ratio, *ratio),
+            Self::Progress(cmp, progress) => cmp.eval(torrent.progress, *progress),
+            Self::Size(cmp, size) => cmp.eval(torrent.size, *size),
+            Self::SeedingTime(cmp, seeding_time) => {
+                cmp.eval(torrent.seeding_time.max(0) as u64, seeding_time.as_secs())
+            }
+            Self::Age(cmp, age) => {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                cmp.eval(now.saturating_sub(torrent.added_on), age.as_secs())
+            }
+            Self::And(selectors) => selectors.iter().all(|s| s.matches(torrent)),
+            Self::Or(selectors) => selectors.iter().any(|s| s.matches(torrent)),
+            Self::Not(selector) => !selector.matches(torrent),
+        }
+    }
+
+    /// Split into the query sent to `torrents/info` and the selector left to evaluate locally
+    ///
+    /// only conditions on the top level `&` are pushed down, at most one of each kind.
+    /// An empty `Hashes` is never sent, qBittorrent reads `hashes=` as no filter at all
+    pub fn split(&self) -> (InfoQuery, TorrentSelector) {
+        let mut query = InfoQuery::default();
+        let mut rest = TorrentSelector::All;
+        let selectors = match self {
+            Self::And(selectors) => selectors.as_slice(),
+            selector => std::slice::from_ref(selector),
+        };
+        for selector in selectors {
+            match selector {
+                Self::All => {}
+                Self::Hashes(hashes) if !hashes.is_empty() && query.hashes.is_none() => {
+                    query.hashes = Some(hashes.clone());
+                }
+                Self::Category(categories) if categories.len() == 1 && query.category.is_none() => {
+                    query.category = Some(categories[0].clone());
+                }
+                Self::Tag(tag) if query.tag.is_none() => {
+                    query.tag = Some(tag.clone());
+                }
+                Self::Filter(filter) if query.filter.is_none() => {
+                    query.filter = Some(filter.clone());
+                }
+                selector => rest = rest.and(selector.clone()),
+            }
+        }
+        (query, rest)
+    }
+
+    /// Whether the top level `&` requires an empty `Hashes`, nothing can match then
+    pub(crate) fn selects_nothing(&self) -> bool {
+        let selectors = match self {
+            Self::And(selectors) => selectors.as_slice(),
+            selector => std::slice::from_ref(selector),
+        };
+        selectors
+            .iter()
+            .any(|s| matches!(s, Self::Hashes(hashes) if hashes.is_empty()))
+    }
+}
+
+impl BitAnd for TorrentSelector {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.and(rhs)
+    }
+}
+
+impl BitOr for TorrentSelector {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.or(rhs)
+    }
+}
+
+impl Not for TorrentSelector {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        match self {
+            Self::Not(selector) => *selector,
+            selector => Self::Not(Box::new(selector)),
+        }
+    }
+}
+
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+/// the `filter` of `torrents/info`, as qBittorrent evaluates it
+fn filter_matches(filter: &InfoFilter, t: &InfoResponseItem) -> bool {
+    use InfoState::*;
+    let paused = matches!(t.state, PausedDL | PausedUP);
+    let active = t.dlspeed > 0 || t.upspeed > 0;
+    match filter {
+        InfoFilter::All => true,
+        InfoFilter::Downloading => matches!(
+            t.state,
+            Downloading | MetaDL | StalledDL | CheckingDL | PausedDL | QueuedDL | ForceDL
+        ),
+        InfoFilter::Seeding => {
+            matches!(
+                t.state,
+                Uploading | StalledUP | CheckingUP | QueuedUP | ForcedUP
+            )
+        }
+        InfoFilter::Completed => matches!(
+            t.state,
+            Uploading | StalledUP | CheckingUP | PausedUP | QueuedUP | ForcedUP
+        ),
+        InfoFilter::Paused => paused,
+        InfoFilter::Resumed => !paused,
+        InfoFilter::Active => active,
+        InfoFilter::Inactive => !active,
+        InfoFilter::Stalled => matches!(t.state, StalledDL | StalledUP),
+        InfoFilter::StalledUploading => t.state == StalledUP,
+        InfoFilter::StalledDownloading => t.state == StalledDL,
+        InfoFilter::Errored => matches!(t.state, Error | MissingFiles),
+    }
+}
+
+/// The torrents a bulk operation like `torrents_pause` applies to
+///
+/// hashes convert into it, so existing calls passing `&[hash]` keep working
+#[derive(Debug, Clone)]
+pub enum TorrentTargets {
+    Hashes(Vec<String>),
+    /// every torrent, sent as `all`
+    All,
+    /// the torrents matching the selector when the operation is called
+    Selector(TorrentSelector),
+}
+
+impl<T: AsRef<str>> From<&[T]> for TorrentTargets {
+    fn from(hashes: &[T]) -> Self {
+        Self::Hashes(hashes.iter().map(|h| h.as_ref().to_owned()).collect())
+    }
+}
+
+impl<T: AsRef<str>, const N: usize> From<&[T; N]> for TorrentTargets {
+    fn from(hashes: &[T; N]) -> Self {
+        Self::from(hashes.as_slice())
+    }
+}
+
+impl<T: AsRef<str>> From<&Vec<T>> for TorrentTargets {
+    fn from(hashes: &Vec<T>) -> Self {
+        Self::from(hashes.as_slice())
+    }
+}
+
+impl<T: AsRef<str>> From<Vec<T>> for TorrentTargets {
+    fn from(hashes: Vec<T>) -> Self {
+        Self::from(hashes.as_slice())
+    }
+}
+
+impl From<TorrentSelector> for TorrentTargets {
+    fn from(selector: TorrentSelector) -> Self {
+        match selector {
+            TorrentSelector::All => Self::All,
+            selector => Self::Selector(selector),
+        }
+    }
+}
+
+impl From<&TorrentSelector> for TorrentTargets {
+    fn from(selector: &TorrentSelector) -> Self {
+        Self::from(selector.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let selector = TorrentSelector::category("linux")
+            & TorrentSelector::tag("iso")
+            & TorrentSelector::tag("keep")
+            & TorrentSelector::ratio(Cmp::Gt, 2.0);
+        let (query, rest) = selector.split();
+        assert_eq!(query.category.as_deref(), Some("linux"));
+        assert_eq!(query.tag.as_deref(), Some("iso"));
+        assert!(query.filter.is_none());
+        let TorrentSelector::And(rest) = rest else {
+            panic!("expected the second tag and the ratio left, got {:?}", rest);
+        };
+        assert!(matches!(rest[0], TorrentSelector::Tag(ref t) if t == "keep"));
+        assert!(matches!(rest[1], TorrentSelector::Ratio(Cmp::Gt, _)));
+
+        // nothing is pushed down out of `|` or `!`
+        let selector = TorrentSelector::category("tv") | !TorrentSelector::tag("keep");
+        let (query, rest) = selector.split();
+        assert!(query.category.is_none() && query.tag.is_none());
+        assert!(matches!(rest, TorrentSelector::Or(_)));
+
+        // more than one category is evaluated locally
+        let (query, rest) = TorrentSelector::category_in(["tv", "movies"]).split();
+        assert!(query.category.is_none());
+        assert!(matches!(rest, TorrentSelector::Category(_)));
+
+        // an empty hash list is never sent as `hashes=`
+        let selector = TorrentSelector::hashes(Vec::<String>::new()) & TorrentSelector::tag("iso");
+        let (query, rest) = selector.split();
+        assert!(query.hashes.is_none());
+        assert_eq!(query.tag.as_deref(), Some("iso"));
+        assert!(matches!(rest, TorrentSelector::Hashes(ref h) if h.is_empty()));
+        assert!(selector.selects_nothing());
+    }
+}
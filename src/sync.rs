@@ -0,0 +1,688 @@
+//! A stateful view of `/api/v2/sync/maindata`
+//!
+//! qBittorrent answers `sync/maindata` with the difference since the `rid` of the last response.
+//! [`SyncState`] keeps the `rid` and merges every response into a complete state,
+//! the changes are reported as [`SyncEvent`]s, see also [`SyncState::into_events`].
+//!
+//! ```no_run
+//! use qbit_api_rs::{client::{QbitApi, QbitClient}, sync::SyncState};
+//!
+//! # async fn run() -> Result<(), qbit_api_rs::error::ClientError> {
+//! let client = QbitClient::new_from_env()?;
+//! client.auth_login().await?;
+//!
+//! let mut state = SyncState::new();
+//! loop {
+//!     state.sync(&client).await?;
+//!     for (hash, torrent) in state.torrents() {
+//!         println!("{} {:?} {:?}", hash, torrent.name, torrent.progress);
+//!     }
+//!     # break;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::QbitApi;
+use crate::error::ClientError;
+use crate::types::{
+    sync::{
+        MaindataCategory, MaindataQuery, MaindataResponse, MaindataTorrentsResponseItem,
+        ServerState, TorrentPeer, TorrentPeersQuery, TorrentPeersResponse,
+    },
+    torrents::{CategoriesDetails, CategoriesResponse, InfoState},
+};
+use futures_util::stream::{self, Stream};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::time::Duration;
+
+/// used when the server doesn't send `refresh_interval`
+const DEFAULT_REFRESH_INTERVAL: u64 = 1500;
+
+#[derive(Debug, Clone, Default)]
+pub struct SyncState {
+    rid: u64,
+    torrents: HashMap<String, MaindataTorrentsResponseItem>,
+    categories: CategoriesResponse,
+    tags: BTreeSet<String>,
+    server_state: ServerState,
+}
+
+/// A change found while applying a response of `/api/v2/sync/maindata`
+#[derive(Debug, Clone)]
+pub enum SyncEvent {
+    TorrentAdded {
+        hash: String,
+        torrent: MaindataTorrentsResponseItem,
+    },
+    TorrentRemoved {
+        hash: String,
+    },
+    StateChanged {
+        hash: String,
+        old: InfoState,
+        new: InfoState,
+    },
+    ProgressChanged {
+        hash: String,
+        old: f64,
+        new: f64,
+    },
+    /// progress of the torrent reached `1.0`
+    Completed {
+        hash: String,
+        torrent: MaindataTorrentsResponseItem,
+    },
+    CategoryCreated {
+        name: String,
+        category: CategoriesDetails,
+    },
+    CategoryRemoved {
+        name: String,
+    },
+    TagsChanged {
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    ServerStateChanged(ServerState),
+}
+
+impl SyncState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// the `rid` of the last applied response, `0` if nothing has been applied
+    pub fn rid(&self) -> u64 {
+        self.rid
+    }
+
+    /// torrents keyed by hash
+    pub fn torrents(&self) -> &HashMap<String, MaindataTorrentsResponseItem> {
+        &self.torrents
+    }
+
+    pub fn categories(&self) -> &CategoriesResponse {
+        &self.categories
+    }
+
+    pub fn tags(&self) -> &BTreeSet<String> {
+        &self.tags
+    }
+
+    pub fn server_state(&self) -> &ServerState {
+        &self.server_state
+    }
+
+    /// the polling interval suggested by the server
+    pub fn refresh_interval(&self) -> Duration {
+        Duration::from_millis(
+            self.server_state
+                .refresh_interval
+                .unwrap_or(DEFAULT_REFRESH_INTERVAL),
+        )
+    }
+
+    /// Request `/api/v2/sync/maindata` with the current `rid` and apply the response
+    pub async fn sync<A>(&mut self, client: &A) -> Result<Vec<SyncEvent>, ClientError>
+    where
+        A: QbitApi,
+    {
+        let q = MaindataQuery { rid: self.rid };
+        let resp = client.sync_maindata(&q).await?;
+
+        Ok(self.apply(resp))
+    }
+
+    /// Apply a response of `/api/v2/sync/maindata` and return the changes
+    ///
+    /// a full update replaces the whole state, otherwise the response is merged field by field
+    pub fn apply(&mut self, resp: MaindataResponse) -> Vec<SyncEvent> {
+        let mut events = Vec::new();
+        self.rid = resp.rid;
+
+        if resp.full_update.unwrap_or(false) {
+            self.replace(resp, &mut events);
+        } else {
+            self.merge(resp, &mut events);
+        }
+
+        events
+    }
+
+    fn replace(&mut self, resp: MaindataResponse, events: &mut Vec<SyncEvent>) {
+        let old_torrents = std::mem::take(&mut self.torrents);
+        for (hash, torrent) in resp.torrents.map(|t| t.data).unwrap_or_default() {
+            match old_torrents.get(&hash) {
+                Some(old) => {
+                    torrent_changed(&hash, old.state.clone(), old.progress, &torrent, events)
+                }
+                None => events.push(SyncEvent::TorrentAdded {
+                    hash: hash.clone(),
+                    torrent: torrent.clone(),
+                }),
+            }
+            self.torrents.insert(hash, torrent);
+        }
+        for hash in old_torrents.into_keys() {
+            if !self.torrents.contains_key(&hash) {
+                events.push(SyncEvent::TorrentRemoved { hash });
+            }
+        }
+
+        let categories = resp
+            .categories
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, delta)| {
+                let category = new_category(&name, delta);
+                (name, category)
+            })
+            .collect();
+        let old_categories = std::mem::replace(&mut self.categories, categories);
+        for (name, category) in self.categories.iter() {
+            if !old_categories.contains_key(name) {
+                events.push(SyncEvent::CategoryCreated {
+                    name: name.clone(),
+                    category: category.clone(),
+                });
+            }
+        }
+        for name in old_categories.into_keys() {
+            if !self.categories.contains_key(&name) {
+                events.push(SyncEvent::CategoryRemoved { name });
+            }
+        }
+
+        let new_tags: BTreeSet<String> = resp.tags.unwrap_or_default().into_iter().collect();
+        let added: Vec<String> = new_tags.difference(&self.tags).cloned().collect();
+        let removed: Vec<String> = self.tags.difference(&new_tags).cloned().collect();
+        self.tags = new_tags;
+        tags_changed(added, removed, events);
+
+        self.server_state = resp.server_state.unwrap_or_default();
+        if resp.queueing.is_some() {
+            self.server_state.queueing = resp.queueing;
+        }
+        events.push(SyncEvent::ServerStateChanged(self.server_state.clone()));
+    }
+
+    fn merge(&mut self, resp: MaindataResponse, events: &mut Vec<SyncEvent>) {
+        for (hash, delta) in resp.torrents.map(|t| t.data).unwrap_or_default() {
+            match self.torrents.get_mut(&hash) {
+                Some(torrent) => {
+                    let old_state = torrent.state.clone();
+                    let old_progress = torrent.progress;
+                    torrent.merge(delta);
+                    torrent_changed(&hash, old_state, old_progress, torrent, events);
+                }
+                None => {
+                    let mut torrent = MaindataTorrentsResponseItem::default();
+                    torrent.merge(delta);
+                    events.push(SyncEvent::TorrentAdded {
+                        hash: hash.clone(),
+                        torrent: torrent.clone(),
+                    });
+                    self.torrents.insert(hash, torrent);
+                }
+            }
+        }
+        for hash in resp.torrents_removed.unwrap_or_default() {
+            if self.torrents.remove(&hash).is_some() {
+                events.push(SyncEvent::TorrentRemoved { hash });
+            }
+        }
+
+        for (name, delta) in resp.categories.unwrap_or_default() {
+            match self.categories.get_mut(&name) {
+                Some(category) => delta.merge_into(category),
+                None => {
+                    let category = new_category(&name, delta);
+                    events.push(SyncEvent::CategoryCreated {
+                        name: name.clone(),
+                        category: category.clone(),
+                    });
+                    self.categories.insert(name, category);
+                }
+            }
+        }
+        for name in resp.categories_removed.unwrap_or_default() {
+            if self.categories.remove(&name).is_some() {
+                events.push(SyncEvent::CategoryRemoved { name });
+            }
+        }
+
+        let added: Vec<String> = resp
+            .tags
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|tag| self.tags.insert(tag.clone()))
+            .collect();
+        let removed: Vec<String> = resp
+            .tags_removed
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|tag| self.tags.remove(tag))
+            .collect();
+        tags_changed(added, removed, events);
+
+        // `queueing` is sent either at the top level or in `server_state`
+        let queueing_changed = resp.queueing.is_some();
+        if queueing_changed {
+            self.server_state.queueing = resp.queueing;
+        }
+        match resp.server_state {
+            Some(server_state) => {
+                self.server_state.merge(server_state);
+                events.push(SyncEvent::ServerStateChanged(self.server_state.clone()));
+            }
+            None if queueing_changed => {
+                events.push(SyncEvent::ServerStateChanged(self.server_state.clone()));
+            }
+            None => {}
+        }
+    }
+
+    /// Poll `/api/v2/sync/maindata` and yield the changes as a stream
+    ///
+    /// The server is polled again only after all events of the last response are consumed,
+    /// waiting [`SyncState::refresh_interval`] in between. Errors are yielded as they are
+    /// and polling continues, dropping the stream stops polling.
+    ///
+    /// The first response is a full update, so every existing torrent is reported as
+    /// [`SyncEvent::TorrentAdded`] unless the state has been synced before.
+    ///
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use qbit_api_rs::{client::{QbitApi, QbitClient}, sync::{SyncEvent, SyncState}};
+    ///
+    /// # async fn run() -> Result<(), qbit_api_rs::error::ClientError> {
+    /// let client = QbitClient::new_from_env()?;
+    /// client.auth_login().await?;
+    ///
+    /// let events = SyncState::new().into_events(&client);
+    /// futures_util::pin_mut!(events);
+    /// while let Some(event) = events.next().await {
+    ///     if let SyncEvent::Completed { hash, .. } = event? {
+    ///         println!("{} completed", hash);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_events<A>(
+        self,
+        client: &A,
+    ) -> impl Stream<Item = Result<SyncEvent, ClientError>> + '_
+    where
+        A: QbitApi,
+    {
+        let init = (self, VecDeque::new(), true);
+        stream::unfold(init, move |(mut state, mut pending, first)| async move {
+            let mut first = first;
+            loop {
+                if let Some(event) = pending.pop_front() {
+                    return Some((Ok(event), (state, pending, first)));
+                }
+                if !first {
+                    tokio::time::sleep(state.refresh_interval()).await;
+                }
+                first = false;
+                match state.sync(client).await {
+                    Ok(events) => pending.extend(events),
+                    Err(e) => return Some((Err(e), (state, pending, first))),
+                }
+            }
+        })
+    }
+}
+
+/// A stateful view of `/api/v2/sync/torrentPeers` of a single torrent
+#[derive(Debug, Clone, Default)]
+pub struct PeerSyncState {
+    hash: String,
+    rid: u64,
+    peers: HashMap<String, TorrentPeer>,
+    show_flags: Option<bool>,
+}
+
+/// A change found while applying a response of `/api/v2/sync/torrentPeers`
+///
+/// `peer` is the key of the peer, in form of `ip:port`
+#[derive(Debug, Clone)]
+pub enum PeerEvent {
+    Connected {
+        peer: String,
+        info: Box<TorrentPeer>,
+    },
+    Disconnected {
+        peer: String,
+    },
+}
+
+impl PeerSyncState {
+    pub fn new<H>(hash: H) -> Self
+    where
+        H: AsRef<str>,
+    {
+        Self {
+            hash: hash.as_ref().to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// the `rid` of the last applied response, `0` if nothing has been applied
+    pub fn rid(&self) -> u64 {
+        self.rid
+    }
+
+    /// peers keyed by `ip:port`
+    pub fn peers(&self) -> &HashMap<String, TorrentPeer> {
+        &self.peers
+    }
+
+    pub fn show_flags(&self) -> Option<bool> {
+        self.show_flags
+    }
+
+    /// Request `/api/v2/sync/torrentPeers` with the current `rid` and apply the response
+    pub async fn sync<A>(&mut self, client: &A) -> Result<Vec<PeerEvent>, ClientError>
+    where
+        A: QbitApi,
+    {
+        let q = TorrentPeersQuery {
+            hash: self.hash.clone(),
+            rid: self.rid,
+        };
+        let resp = client.sync_torrent_peers(&q).await?;
+
+        Ok(self.apply(resp))
+    }
+
+    /// Apply a response of `/api/v2/sync/torrentPeers` and return the connected and
+    /// disconnected peers
+    pub fn apply(&mut self, resp: TorrentPeersResponse) -> Vec<PeerEvent> {
+        let mut events = Vec::new();
+        self.rid = resp.rid;
+        if resp.show_flags.is_some() {
+            self.show_flags = resp.show_flags;
+        }
+
+        let peers = resp.peers.unwrap_or_default();
+        if resp.full_update.unwrap_or(false) {
+            let old_peers = std::mem::replace(&mut self.peers, peers);
+            for peer in self.peers.keys() {
+                if !old_peers.contains_key(peer) {
+                    events.push(PeerEvent::Connected {
+                        peer: peer.clone(),
+                        info: Box::new(self.peers[peer].clone()),
+                    });
+                }
+            }
+            for peer in old_peers.into_keys() {
+                if !self.peers.contains_key(&peer) {
+                    events.push(PeerEvent::Disconnected { peer });
+                }
+            }
+        } else {
+            for (peer, delta) in peers {
+                match self.peers.get_mut(&peer) {
+                    Some(info) => info.merge(delta),
+                    None => {
+                        events.push(PeerEvent::Connected {
+                            peer: peer.clone(),
+                            info: Box::new(delta.clone()),
+                        });
+                        self.peers.insert(peer, delta);
+                    }
+                }
+            }
+        }
+        for peer in resp.peers_removed.unwrap_or_default() {
+            if self.peers.remove(&peer).is_some() {
+                events.push(PeerEvent::Disconnected { peer });
+            }
+        }
+
+        events
+    }
+}
+
+fn torrent_changed(
+    hash: &str,
+    old_state: Option<InfoState>,
+    old_progress: Option<f64>,
+    torrent: &MaindataTorrentsResponseItem,
+    events: &mut Vec<SyncEvent>,
+) {
+    if let (Some(old), Some(new)) = (old_state, &torrent.state) {
+        if &old != new {
+            events.push(SyncEvent::StateChanged {
+                hash: hash.to_owned(),
+                old,
+                new: new.clone(),
+            });
+        }
+    }
+    if let (Some(old), Some(new)) = (old_progress, torrent.progress) {
+        if old != new {
+            events.push(SyncEvent::ProgressChanged {
+                hash: hash.to_owned(),
+                old,
+                new,
+            });
+            if old < 1.0 && new >= 1.0 {
+                events.push(SyncEvent::Completed {
+                    hash: hash.to_owned(),
+                    torrent: torrent.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn tags_changed(added: Vec<String>, removed: Vec<String>, events: &mut Vec<SyncEvent>) {
+    if !added.is_empty() || !removed.is_empty() {
+        events.push(SyncEvent::TagsChanged { added, removed });
+    }
+}
+
+/// a category seen for the first time, `name` is its key if the server leaves it out
+fn new_category(name: &str, delta: MaindataCategory) -> CategoriesDetails {
+    let mut category = CategoriesDetails {
+        name: name.to_owned(),
+        save_path: String::new(),
+    };
+    delta.merge_into(&mut category);
+    category
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn full() -> MaindataResponse {
+        let s = std::fs::read_to_string("./tests/MaindataResponse.json").unwrap();
+        serde_json::from_str(&s).unwrap()
+    }
+
+    const HASH: &str = "f3f662af354d8e64a2fe488c532e463be2992153";
+
+    #[test]
+    fn test_apply_partial_update() {
+        let mut state = SyncState::new();
+        state.apply(full());
+        assert_eq!(state.rid(), 1);
+        assert!(state.torrents().contains_key(HASH));
+        let name = state.torrents()[HASH].name.clone();
+
+        let delta: MaindataResponse = serde_json::from_str(&format!(
+            r#"{{
+                "rid": 2,
+                "torrents": {{"{HASH}": {{"upspeed": 1024}}}},
+                "categories": {{"tv": {{"name": "tv", "savePath": "/tv"}}}},
+                "tags": ["keep"],
+                "server_state": {{"dl_info_speed": 42}}
+            }}"#
+        ))
+        .unwrap();
+        state.apply(delta);
+
+        assert_eq!(state.rid(), 2);
+        assert_eq!(state.torrents()[HASH].upspeed, Some(1024));
+        assert_eq!(state.torrents()[HASH].name, name);
+        assert_eq!(state.categories()["tv"].save_path, "/tv");
+        assert!(state.tags().contains("keep"));
+        assert_eq!(state.server_state().dl_info_speed, Some(42));
+        assert_eq!(state.server_state().refresh_interval, Some(1500));
+
+        // a changed category only carries the changed fields
+        let delta: MaindataResponse =
+            serde_json::from_str(r#"{"rid": 3, "categories": {"tv": {"savePath": "/x"}}}"#)
+                .unwrap();
+        let events = state.apply(delta);
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, SyncEvent::CategoryCreated { .. })));
+        assert_eq!(state.categories()["tv"].name, "tv");
+        assert_eq!(state.categories()["tv"].save_path, "/x");
+
+        let delta: MaindataResponse = serde_json::from_str(&format!(
+            r#"{{
+                "rid": 4,
+                "torrents_removed": ["{HASH}"],
+                "categories_removed": ["tv"],
+                "tags_removed": ["keep"]
+            }}"#
+        ))
+        .unwrap();
+        state.apply(delta);
+
+        assert!(state.torrents().is_empty());
+        assert!(state.categories().is_empty());
+        assert!(state.tags().is_empty());
+    }
+
+    #[test]
+    fn test_apply_full_update_replaces_state() {
+        let mut state = SyncState::new();
+        state.apply(full());
+
+        let delta: MaindataResponse = serde_json::from_str(
+            r#"{"rid": 5, "full_update": true, "torrents": {}, "tags": ["a"]}"#,
+        )
+        .unwrap();
+        state.apply(delta);
+
+        assert_eq!(state.rid(), 5);
+        assert!(state.torrents().is_empty());
+        assert_eq!(state.server_state().refresh_interval, None);
+        assert!(state.tags().contains("a"));
+    }
+
+    #[test]
+    fn test_apply_events() {
+        let mut state = SyncState::new();
+        let events = state.apply(full());
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, SyncEvent::TorrentAdded { hash, .. } if hash == HASH)));
+
+        let delta: MaindataResponse = serde_json::from_str(&format!(
+            r#"{{
+                "rid": 2,
+                "torrents": {{
+                    "{HASH}": {{"state": "pausedUP"}},
+                    "0000000000000000000000000000000000000000": {{"name": "new", "progress": 0.5}}
+                }},
+                "categories": {{"tv": {{"name": "tv", "savePath": "/tv"}}}},
+                "tags": ["keep"]
+            }}"#
+        ))
+        .unwrap();
+        let events = state.apply(delta);
+        assert!(events.iter().any(|e| matches!(
+            e,
+            SyncEvent::StateChanged {
+                old: InfoState::StalledUP,
+                new: InfoState::PausedUP,
+                ..
+            }
+        )));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, SyncEvent::TorrentAdded { torrent, .. } if torrent.name.as_deref() == Some("new"))));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, SyncEvent::CategoryCreated { name, .. } if name == "tv")));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, SyncEvent::TagsChanged { added, .. } if added == &["keep"])));
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, SyncEvent::ServerStateChanged(_))));
+
+        let delta: MaindataResponse = serde_json::from_str(
+            r#"{"rid": 3, "torrents": {"0000000000000000000000000000000000000000": {"progress": 1}}}"#,
+        )
+        .unwrap();
+        let events = state.apply(delta);
+        assert!(matches!(
+            events[..],
+            [
+                SyncEvent::ProgressChanged { old, new, .. },
+                SyncEvent::Completed { .. }
+            ] if old == 0.5 && new == 1.0
+        ));
+    }
+
+    #[test]
+    fn test_apply_peers() {
+        let mut state = PeerSyncState::new(HASH);
+        let full: TorrentPeersResponse = serde_json::from_str(
+            r#"{
+                "rid": 1,
+                "full_update": true,
+                "show_flags": true,
+                "peers": {
+                    "1.1.1.1:6881": {"client": "qBittorrent", "ip": "1.1.1.1", "port": 6881, "progress": 0.1},
+                    "2.2.2.2:6881": {"client": "Transmission", "ip": "2.2.2.2", "port": 6881, "progress": 1}
+                }
+            }"#,
+        )
+        .unwrap();
+        let events = state.apply(full);
+        assert_eq!(events.len(), 2);
+        assert_eq!(state.show_flags(), Some(true));
+
+        let delta: TorrentPeersResponse = serde_json::from_str(
+            r#"{
+                "rid": 2,
+                "peers": {
+                    "1.1.1.1:6881": {"progress": 0.2},
+                    "3.3.3.3:6881": {"ip": "3.3.3.3", "port": 6881}
+                },
+                "peers_removed": ["2.2.2.2:6881"]
+            }"#,
+        )
+        .unwrap();
+        let events = state.apply(delta);
+        assert_eq!(state.rid(), 2);
+        assert_eq!(state.peers()["1.1.1.1:6881"].progress, Some(0.2));
+        assert_eq!(
+            state.peers()["1.1.1.1:6881"].client.as_deref(),
+            Some("qBittorrent")
+        );
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, PeerEvent::Connected { peer, .. } if peer == "3.3.3.3:6881")));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, PeerEvent::Disconnected { peer } if peer == "2.2.2.2:6881")));
+        assert_eq!(events.len(), 2);
+    }
+}
@@ -3,6 +3,51 @@ use serde_repr::*;
 use std::collections::HashMap;
 use super::preferences_serialize;
 
+/// # `/api/v2/app/webapiVersion`
+///
+/// parsed from e.g. `2.9.3`, a missing patch counts as `0`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WebApiVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl WebApiVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl std::str::FromStr for WebApiVersion {
+    type Err = crate::error::TypesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || crate::error::TypesError::Other(format!("invalid Web API version: {}", s));
+        let mut parts = s.trim().trim_start_matches('v').split('.');
+        let mut next = |required: bool| match parts.next() {
+            Some(part) => part.parse::<u32>().map_err(|_| err()),
+            None if required => Err(err()),
+            None => Ok(0),
+        };
+        let version = Self::new(next(true)?, next(true)?, next(false)?);
+        match parts.next() {
+            Some(_) => Err(err()),
+            None => Ok(version),
+        }
+    }
+}
+
+impl std::fmt::Display for WebApiVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
 /// # `/api/v2/app/buildInfo`
 #[derive(Debug, Clone, Deserialize)]
 pub struct BuildInfoResponse {
@@ -1,6 +1,7 @@
 pub mod app;
 pub mod auth;
 pub mod log;
+pub mod rss;
 pub mod search;
 pub mod sync;
 pub mod torrents;
@@ -86,6 +87,22 @@ mod string_saperated_with_comma {
     }
 }
 
+/// module to serialize `Vec<u64>` as String with vertical bar `|`
+///
+/// e.g. `vec![1, 2, 3] => "1|2|3"`
+mod u64_saperated_with_vertical_bar {
+
+    use serde::Serializer;
+
+    pub fn serialize<S>(items: &[u64], s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let items: Vec<String> = items.iter().map(|i| i.to_string()).collect();
+        s.serialize_str(&items.join("|"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,4 +176,170 @@ mod tests {
             r#"{"hashes":"7e2fc0391f2d855affed3b0545927bddd5189bc6","ratioLimit":-1,"seedingTimeLimit":0}"#
         );
     }
+
+    #[test]
+    fn test_parse_webapi_version() {
+        let v: app::WebApiVersion = "2.8.3".parse().unwrap();
+        assert_eq!(v, app::WebApiVersion::new(2, 8, 3));
+        assert_eq!(
+            "v2.11".parse::<app::WebApiVersion>().unwrap().to_string(),
+            "2.11.0"
+        );
+        assert!(v < "2.8.18".parse().unwrap());
+        assert!("2".parse::<app::WebApiVersion>().is_err());
+        assert!("2.x.1".parse::<app::WebApiVersion>().is_err());
+    }
+
+    #[test]
+    fn test_add_torrent_options() {
+        use torrents::{AddTorrentOptions, ContentLayout, StopCondition};
+
+        let urls = || vec!["magnet:?xt=urn:btih:7e2fc0391f2d855affed3b0545927bddd5189bc6".into()];
+        let options = AddTorrentOptions::new()
+            .paused(true)
+            .content_layout(ContentLayout::Subfolder)
+            .stop_condition(StopCondition::FilesChecked)
+            .download_path("/incomplete");
+
+        let mp = options
+            .to_multipart(urls(), vec![], Some(app::WebApiVersion::new(2, 11, 0)))
+            .unwrap();
+        assert_eq!((mp.paused, mp.stopped), (None, Some(true)));
+        assert_eq!(mp.content_layout, Some(ContentLayout::Subfolder));
+        assert_eq!(mp.stop_condition, Some(StopCondition::FilesChecked));
+        assert_eq!(mp.download_path.as_deref(), Some("/incomplete"));
+
+        let mp = options
+            .to_multipart(urls(), vec![], Some(app::WebApiVersion::new(2, 6, 0)))
+            .unwrap();
+        assert_eq!((mp.paused, mp.stopped), (Some(true), None));
+        assert_eq!((mp.root_folder, mp.content_layout), (Some(true), None));
+        assert_eq!((mp.stop_condition, mp.download_path), (None, None));
+
+        let mp = options.to_multipart(urls(), vec![], None).unwrap();
+        assert_eq!((mp.paused, mp.stopped), (Some(true), Some(true)));
+        assert_eq!(
+            (mp.root_folder, mp.content_layout),
+            (None, Some(ContentLayout::Subfolder))
+        );
+
+        let mp = AddTorrentOptions::new()
+            .root_folder(false)
+            .to_multipart(urls(), vec![], Some(app::WebApiVersion::new(2, 9, 3)))
+            .unwrap();
+        assert_eq!(
+            (mp.root_folder, mp.content_layout),
+            (None, Some(ContentLayout::NoSubfolder))
+        );
+
+        let invalid = [
+            AddTorrentOptions::new().savepath("/data").auto_tmm(true),
+            AddTorrentOptions::new()
+                .download_path("/data")
+                .use_download_path(false),
+            AddTorrentOptions::new()
+                .root_folder(true)
+                .content_layout(ContentLayout::Original),
+            AddTorrentOptions::new().tags(["a,b"]),
+            AddTorrentOptions::new().ratio_limit(-0.5),
+        ];
+        for options in invalid {
+            assert!(options.validate().is_err(), "{:?}", options);
+        }
+        let two = vec![
+            "https://example.com/a.torrent".into(),
+            "https://example.com/b.torrent".into(),
+        ];
+        assert!(AddTorrentOptions::new()
+            .rename("a")
+            .to_multipart(two, vec![], None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_deserialize_rss_items() {
+        let s = r#"{
+            "Folder": {
+                "Feed A": {"uid": "{2c8ec0e5}", "url": "https://example.com/a.xml"}
+            },
+            "Feed B": {
+                "uid": "{8b4b0d2a}",
+                "url": "https://example.com/b.xml",
+                "title": "B",
+                "isLoading": false,
+                "hasError": false,
+                "articles": [{"id": "1", "title": "ubuntu", "torrentURL": "https://example.com/1.torrent", "isRead": true}]
+            }
+        }"#;
+        let items: rss::ItemsResponse = serde_json::from_str(s).unwrap();
+        match &items["Folder"] {
+            rss::Item::Folder(children) => {
+                assert!(matches!(children["Feed A"], rss::Item::Feed(_)))
+            }
+            rss::Item::Feed(_) => panic!("`Folder` should not be a feed"),
+        }
+        match &items["Feed B"] {
+            rss::Item::Feed(feed) => {
+                let articles = feed.articles.as_ref().unwrap();
+                assert_eq!(
+                    articles[0].torrent_url.as_deref(),
+                    Some("https://example.com/1.torrent")
+                );
+            }
+            rss::Item::Folder(_) => panic!("`Feed B` should not be a folder"),
+        }
+    }
+
+    #[test]
+    fn test_set_rule_form() {
+        let f = rss::SetRuleForm {
+            rule_name: "ubuntu".to_string(),
+            rule_def: rss::Rule {
+                enabled: Some(true),
+                must_contain: Some("ubuntu".to_string()),
+                affected_feeds: Some(vec!["https://example.com/a.xml".to_string()]),
+                ..Default::default()
+            },
+        };
+        let s = serde_json::to_string(&f).unwrap();
+        assert_eq!(
+            s,
+            r#"{"ruleName":"ubuntu","ruleDef":"{\"enabled\":true,\"mustContain\":\"ubuntu\",\"affectedFeeds\":[\"https://example.com/a.xml\"]}"}"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_search_plugins() {
+        let s = r#"[
+            {"enabled": true, "fullName": "Legit Torrents", "name": "legittorrents",
+             "supportedCategories": [{"id": "all", "name": "All categories"}, {"id": "tv", "name": "TV shows"}],
+             "url": "http://www.legittorrents.info", "version": "2.3"},
+            {"enabled": false, "fullName": "Old", "name": "old",
+             "supportedCategories": ["all", "movies"],
+             "url": "http://example.com", "version": "1.0"}
+        ]"#;
+        let plugins: search::PluginsResponse = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            plugins[0].supported_categories[1],
+            search::PluginCategory {
+                id: "tv".to_string(),
+                name: "TV shows".to_string()
+            }
+        );
+        assert_eq!(plugins[1].supported_categories[1].id, "movies");
+    }
+
+    #[test]
+    fn test_file_prio_form() {
+        let f = torrents::FilePrioForm {
+            hash: "7e2fc0391f2d855affed3b0545927bddd5189bc6".to_string(),
+            id: vec![0, 2, 5],
+            priority: torrents::FilesPriority::Maximal,
+        };
+        let s = serde_json::to_string(&f).unwrap();
+        assert_eq!(
+            s,
+            r#"{"hash":"7e2fc0391f2d855affed3b0545927bddd5189bc6","id":"0|2|5","priority":7}"#
+        );
+    }
 }
@@ -0,0 +1,174 @@
+use super::preferences_serialize;
+use serde::{self, Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// # `/api/v2/rss/addFolder`
+///
+/// `path` is the full path of added folder, use `\` as separator (e.g. "Folder\Subfolder")
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AddFolderForm {
+    pub path: String,
+}
+
+/// # `/api/v2/rss/addFeed`
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AddFeedForm {
+    pub url: String,
+    /// full path of added feed, use `\` as separator (e.g. "Folder\Feed name")
+    pub path: Option<String>,
+}
+
+/// # `/api/v2/rss/removeItem`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RemoveItemForm {
+    pub path: String,
+}
+
+/// # `/api/v2/rss/moveItem`
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveItemForm {
+    pub item_path: String,
+    pub dest_path: String,
+}
+
+/// # `/api/v2/rss/items`
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemsQuery {
+    pub with_data: bool,
+}
+
+/// # `/api/v2/rss/items`
+///
+/// the root folder, keyed by the name of feeds and sub-folders
+pub type ItemsResponse = HashMap<String, Item>;
+
+/// # `/api/v2/rss/items`
+/// [`ItemsResponse`]
+///
+/// `Note`: a folder is a plain json object of its children,
+/// so `Feed` must be tried first
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Item {
+    Feed(Feed),
+    Folder(HashMap<String, Item>),
+}
+
+/// # `/api/v2/rss/items`
+/// [`Item::Feed`]
+///
+/// fields except `uid` and `url` are only returned when `withData` is `true`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Feed {
+    pub uid: String,
+    pub url: String,
+    pub title: Option<String>,
+    pub last_build_date: Option<String>,
+    pub is_loading: Option<bool>,
+    pub has_error: Option<bool>,
+    pub articles: Option<Vec<Article>>,
+}
+
+/// # `/api/v2/rss/items`
+/// [`Feed::articles`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Article {
+    pub id: String,
+    pub date: Option<String>,
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename = "torrentURL")]
+    pub torrent_url: Option<String>,
+    pub link: Option<String>,
+    pub category: Option<String>,
+    pub is_read: Option<bool>,
+}
+
+/// # `/api/v2/rss/markAsRead`
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkAsReadForm {
+    pub item_path: String,
+    /// if omitted, all articles of the item are marked as read
+    pub article_id: Option<String>,
+}
+
+/// # `/api/v2/rss/refreshItem`
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshItemForm {
+    pub item_path: String,
+}
+
+/// # `/api/v2/rss/setRule`
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRuleForm {
+    pub rule_name: String,
+    #[serde(with = "preferences_serialize")]
+    pub rule_def: Rule,
+}
+
+/// # `/api/v2/rss/setRule`
+/// [`SetRuleForm::rule_def`]
+///
+/// also used in `/api/v2/rss/rules`
+///
+/// `Note`: fields left as `None` are not sent, qBittorrent will use its default values
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    pub enabled: Option<bool>,
+    pub must_contain: Option<String>,
+    pub must_not_contain: Option<String>,
+    pub use_regex: Option<bool>,
+    pub episode_filter: Option<String>,
+    pub smart_filter: Option<bool>,
+    pub previously_matched_episodes: Option<Vec<String>>,
+    pub affected_feeds: Option<Vec<String>>,
+    pub ignore_days: Option<i64>,
+    pub last_match: Option<String>,
+    pub add_paused: Option<bool>,
+    pub assigned_category: Option<String>,
+    pub save_path: Option<String>,
+}
+
+/// # `/api/v2/rss/renameRule`
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameRuleForm {
+    pub rule_name: String,
+    pub new_rule_name: String,
+}
+
+/// # `/api/v2/rss/removeRule`
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveRuleForm {
+    pub rule_name: String,
+}
+
+/// # `/api/v2/rss/rules`
+///
+/// keyed by rule name
+pub type RulesResponse = HashMap<String, Rule>;
+
+/// # `/api/v2/rss/matchingArticles`
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchingArticlesQuery {
+    pub rule_name: String,
+}
+
+/// # `/api/v2/rss/matchingArticles`
+///
+/// keyed by feed name, the values are titles of matching articles
+pub type MatchingArticlesResponse = HashMap<String, Vec<String>>;
@@ -1,11 +1,144 @@
+use super::string_saperated_with_vertical_bar;
 use serde::{self, Deserialize, Serialize};
 
 /// # `api/v2/search/start`
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct StartForm {
     pub pattern: String,
-    pub plugins: String,
-    pub category: String,
+    pub plugins: PluginSelection,
+    pub category: SearchCategory,
+}
+
+/// # `api/v2/search/start`
+/// [`StartForm::plugins`]
+///
+/// a string converts into it, `all`, `enabled` or names separated by `|`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum PluginSelection {
+    /// every installed plugin, enabled or not
+    #[default]
+    All,
+    Enabled,
+    /// names of the plugins, as in [`Plugin::name`]
+    Named(Vec<String>),
+}
+
+impl std::fmt::Display for PluginSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::All => f.write_str("all"),
+            Self::Enabled => f.write_str("enabled"),
+            Self::Named(names) => f.write_str(&names.join("|")),
+        }
+    }
+}
+
+impl Serialize for PluginSelection {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        s.collect_str(self)
+    }
+}
+
+impl From<&str> for PluginSelection {
+    fn from(plugins: &str) -> Self {
+        match plugins {
+            "all" => Self::All,
+            "enabled" => Self::Enabled,
+            names => Self::Named(names.split('|').map(|n| n.to_owned()).collect()),
+        }
+    }
+}
+
+impl From<String> for PluginSelection {
+    fn from(plugins: String) -> Self {
+        Self::from(plugins.as_str())
+    }
+}
+
+impl From<&String> for PluginSelection {
+    fn from(plugins: &String) -> Self {
+        Self::from(plugins.as_str())
+    }
+}
+
+/// # `api/v2/search/start`
+/// [`StartForm::category`]
+///
+/// the categories known by qBittorrent, a plugin may support others,
+/// see [`Plugin::supported_categories`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SearchCategory {
+    #[default]
+    All,
+    Anime,
+    Books,
+    Games,
+    Movies,
+    Music,
+    Pictures,
+    Software,
+    Tv,
+    /// the id of any other category
+    Other(String),
+}
+
+impl SearchCategory {
+    /// the id sent to qBittorrent, as in [`PluginCategory::id`]
+    pub fn id(&self) -> &str {
+        match self {
+            Self::All => "all",
+            Self::Anime => "anime",
+            Self::Books => "books",
+            Self::Games => "games",
+            Self::Movies => "movies",
+            Self::Music => "music",
+            Self::Pictures => "pictures",
+            Self::Software => "software",
+            Self::Tv => "tv",
+            Self::Other(id) => id,
+        }
+    }
+}
+
+impl Serialize for SearchCategory {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        s.serialize_str(self.id())
+    }
+}
+
+impl From<&str> for SearchCategory {
+    fn from(id: &str) -> Self {
+        match id {
+            "all" => Self::All,
+            "anime" => Self::Anime,
+            "books" => Self::Books,
+            "games" => Self::Games,
+            "movies" => Self::Movies,
+            "music" => Self::Music,
+            "pictures" => Self::Pictures,
+            "software" => Self::Software,
+            "tv" => Self::Tv,
+            id => Self::Other(id.to_owned()),
+        }
+    }
+}
+
+impl From<String> for SearchCategory {
+    fn from(id: String) -> Self {
+        Self::from(id.as_str())
+    }
+}
+
+impl From<&String> for SearchCategory {
+    fn from(id: &String) -> Self {
+        Self::from(id.as_str())
+    }
 }
 
 /// # `api/v2/search/start`
@@ -35,10 +168,18 @@ pub type StatusResponse = Vec<StatusResponseItem>;
 #[derive(Debug, Clone, Deserialize)]
 pub struct StatusResponseItem {
     pub id: u64,
-    pub status: String,
+    pub status: SearchStatus,
     pub total: u64,
 }
 
+/// # `/api/v2/search/status`
+/// [`StatusResponseItem::status`] and [`ResultsResponse::status`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SearchStatus {
+    Running,
+    Stopped,
+}
+
 /// # `/api/v2/search/results`
 #[serde_with::skip_serializing_none]
 #[derive(Debug, Clone, Default, Serialize)]
@@ -52,7 +193,7 @@ pub struct ResultsQuery {
 #[derive(Debug, Clone, Deserialize)]
 pub struct ResultsResponse {
     pub results: Vec<ResultsResponseItem>,
-    pub status: String,
+    pub status: SearchStatus,
     pub total: u64,
 }
 
@@ -75,3 +216,74 @@ pub struct ResultsResponseItem {
 pub struct DeleteForm {
     pub id: u64,
 }
+
+/// # `/api/v2/search/plugins`
+pub type PluginsResponse = Vec<Plugin>;
+
+/// # `/api/v2/search/plugins`
+/// [`PluginsResponse`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Plugin {
+    pub enabled: bool,
+    pub full_name: String,
+    pub name: String,
+    pub supported_categories: Vec<PluginCategory>,
+    pub url: String,
+    pub version: String,
+}
+
+/// # `/api/v2/search/plugins`
+/// [`Plugin::supported_categories`]
+///
+/// `Note`: before qBittorrent 4.3.0 the categories are returned as plain names,
+/// in which case `id` equals to `name`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "PluginCategoryRepr")]
+pub struct PluginCategory {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PluginCategoryRepr {
+    Object { id: String, name: String },
+    Name(String),
+}
+
+impl From<PluginCategoryRepr> for PluginCategory {
+    fn from(repr: PluginCategoryRepr) -> Self {
+        match repr {
+            PluginCategoryRepr::Object { id, name } => Self { id, name },
+            PluginCategoryRepr::Name(name) => Self {
+                id: name.clone(),
+                name,
+            },
+        }
+    }
+}
+
+/// # `/api/v2/search/installPlugin`
+///
+/// `sources` are urls or file paths of the plugins
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InstallPluginForm {
+    #[serde(serialize_with = "string_saperated_with_vertical_bar::serialize")]
+    pub sources: Vec<String>,
+}
+
+/// # `/api/v2/search/uninstallPlugin`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UninstallPluginForm {
+    #[serde(serialize_with = "string_saperated_with_vertical_bar::serialize")]
+    pub names: Vec<String>,
+}
+
+/// # `/api/v2/search/enablePlugin`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EnablePluginForm {
+    #[serde(serialize_with = "string_saperated_with_vertical_bar::serialize")]
+    pub names: Vec<String>,
+    pub enable: bool,
+}
@@ -1,8 +1,19 @@
-use super::torrents::{CategoriesResponse, InfoState};
+use super::torrents::{CategoriesDetails, InfoState};
 use super::transfer::ConnectionStatus;
 use serde::{self, Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// overwrite the fields of `$target` with the fields of `$delta` which are `Some`
+macro_rules! merge_some {
+    ($target:expr, $delta:expr, $($field:ident),+ $(,)?) => {
+        $(
+            if $delta.$field.is_some() {
+                $target.$field = $delta.$field;
+            }
+        )+
+    };
+}
+
 /// # `/api/v2/sync/maindata`
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct MaindataQuery {
@@ -16,7 +27,7 @@ pub struct MaindataResponse {
     pub full_update: Option<bool>,
     pub torrents: Option<MaindataTorrentsResponse>,
     pub torrents_removed: Option<Vec<String>>,
-    pub categories: Option<CategoriesResponse>, // Note: catagories are not mentioned in docs
+    pub categories: Option<HashMap<String, MaindataCategory>>, // Note: catagories are not mentioned in docs
     pub categories_removed: Option<Vec<String>>,
     pub tags: Option<Vec<String>>,
     pub tags_removed: Option<Vec<String>>,
@@ -24,6 +35,29 @@ pub struct MaindataResponse {
     pub server_state: Option<ServerState>, // Note: server_state are not mentioned in docs
 }
 
+/// # `/api/v2/sync/maindata`
+/// [`MaindataResponse::categories`]
+///
+/// a partial update only carries the fields of a category which changed
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaindataCategory {
+    pub name: Option<String>,
+    pub save_path: Option<String>,
+}
+
+impl MaindataCategory {
+    /// merge a partial update into `category`, fields absent in `self` are left unchanged
+    pub fn merge_into(self, category: &mut CategoriesDetails) {
+        if let Some(name) = self.name {
+            category.name = name;
+        }
+        if let Some(save_path) = self.save_path {
+            category.save_path = save_path;
+        }
+    }
+}
+
 /// # `/api/v2/sync/maindata`
 /// [`MaindataResponse::torrents`]
 #[derive(Debug, Clone, Deserialize)]
@@ -36,7 +70,7 @@ pub struct MaindataTorrentsResponse {
 /// [`MaindataTorrentsResponse::data`]
 ///
 /// `Note`: similar to `TorrentsInfoResponseItem` but no `hash` field
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct MaindataTorrentsResponseItem {
     pub added_on: Option<u64>,
     pub amount_left: Option<u64>,
@@ -82,9 +116,60 @@ pub struct MaindataTorrentsResponseItem {
     pub upspeed: Option<i64>,
 }
 
+impl MaindataTorrentsResponseItem {
+    /// merge a partial update, fields absent in `delta` are left unchanged
+    pub fn merge(&mut self, delta: Self) {
+        merge_some!(
+            self,
+            delta,
+            added_on,
+            amount_left,
+            auto_tmm,
+            category,
+            completed,
+            completion_on,
+            dl_limit,
+            dlspeed,
+            downloaded,
+            downloaded_session,
+            eta,
+            f_l_piece_prio,
+            force_start,
+            last_activity,
+            magnet_uri,
+            max_ratio,
+            max_seeding_time,
+            name,
+            num_complete,
+            num_incomplete,
+            num_leechs,
+            num_seeds,
+            priority,
+            progress,
+            ratio,
+            ratio_limit,
+            save_path,
+            seeding_time_limit,
+            seen_complete,
+            seq_dl,
+            size,
+            state,
+            super_seeding,
+            tags,
+            time_active,
+            total_size,
+            tracker,
+            up_limit,
+            uploaded,
+            uploaded_session,
+            upspeed,
+        );
+    }
+}
+
 /// # `/api/v2/sync/maindata`
 /// [`MaindataResponse::server_state`]
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct ServerState {
     pub alltime_dl: Option<u64>,
     pub alltime_ul: Option<u64>,
@@ -113,6 +198,40 @@ pub struct ServerState {
     pub connection_status: Option<ConnectionStatus>,
 }
 
+impl ServerState {
+    /// merge a partial update, fields absent in `delta` are left unchanged
+    pub fn merge(&mut self, delta: Self) {
+        merge_some!(
+            self,
+            delta,
+            alltime_dl,
+            alltime_ul,
+            average_time_queue,
+            free_space_on_disk,
+            global_ratio,
+            queued_io_jobs,
+            queueing,
+            read_cache_hits,
+            read_cache_overload,
+            refresh_interval,
+            total_buffers_size,
+            total_peer_connections,
+            total_queued_size,
+            total_wasted_session,
+            use_alt_speed_limits,
+            write_cache_overload,
+            dl_info_speed,
+            dl_info_data,
+            up_info_speed,
+            up_info_data,
+            dl_rate_limit,
+            up_rate_limit,
+            dht_nodes,
+            connection_status,
+        );
+    }
+}
+
 /// # `/api/v2/sync/torrentPeers`
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct TorrentPeersQuery {
@@ -122,31 +241,62 @@ pub struct TorrentPeersQuery {
 
 /// # `/api/v2/sync/torrentPeers`
 ///
-/// `Note`: The response's format remains TODO status in docs
+/// `Note`: The response's format remains TODO status in docs.
+/// like `/api/v2/sync/maindata`, only changed fields are sent unless `full_update` is `true`
 #[derive(Debug, Clone, Default, PartialEq, Deserialize)]
 pub struct TorrentPeersResponse {
-    pub full_update: bool,
-    pub peers: HashMap<String, TorrentPeer>,
+    pub rid: u64,
+    pub full_update: Option<bool>,
+    /// keyed by `ip:port`
+    pub peers: Option<HashMap<String, TorrentPeer>>,
+    pub peers_removed: Option<Vec<String>>,
+    pub show_flags: Option<bool>,
 }
 
 /// # `/api/v2/sync/torrentPeers`
 /// [`TorrentPeersResponse::peers`]
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
 pub struct TorrentPeer {
-    pub client: String,
-    pub connection: String,
-    pub country: String,
-    pub country_code: String,
-    pub dl_speed: u64,
-    pub downloaded: u64,
-    pub files: String,
-    pub flags: String,
-    pub flags_desc: String,
-    pub ip: String,
-    pub peer_id_client: String,
-    pub port: u16,
-    pub progress: f64,
-    pub relevance: f64,
-    pub up_speed: u64,
-    pub uploaded: u64,
+    pub client: Option<String>,
+    pub connection: Option<String>,
+    pub country: Option<String>,
+    pub country_code: Option<String>,
+    pub dl_speed: Option<u64>,
+    pub downloaded: Option<u64>,
+    pub files: Option<String>,
+    pub flags: Option<String>,
+    pub flags_desc: Option<String>,
+    pub ip: Option<String>,
+    pub peer_id_client: Option<String>,
+    pub port: Option<u16>,
+    pub progress: Option<f64>,
+    pub relevance: Option<f64>,
+    pub up_speed: Option<u64>,
+    pub uploaded: Option<u64>,
+}
+
+impl TorrentPeer {
+    /// merge a partial update, fields absent in `delta` are left unchanged
+    pub fn merge(&mut self, delta: Self) {
+        merge_some!(
+            self,
+            delta,
+            client,
+            connection,
+            country,
+            country_code,
+            dl_speed,
+            downloaded,
+            files,
+            flags,
+            flags_desc,
+            ip,
+            peer_id_client,
+            port,
+            progress,
+            relevance,
+            up_speed,
+            uploaded,
+        );
+    }
 }
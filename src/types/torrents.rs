@@ -1,11 +1,17 @@
+use super::app::WebApiVersion;
 use super::{
     string_saperated_with_backslash_n, string_saperated_with_comma,
-    string_saperated_with_vertical_bar,
+    string_saperated_with_vertical_bar, u64_saperated_with_vertical_bar,
 };
 use crate::error::TypesError;
+use crate::magnet::MagnetLink;
+use bytes::Bytes;
 use serde::{self, Deserialize, Serialize};
 use serde_repr::*;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncRead;
 
 /// # `/api/v2/torrents/info`
 #[serde_with::skip_serializing_none]
@@ -122,6 +128,12 @@ pub struct InfoResponseItem {
     pub f_l_piece_prio: bool,
     pub force_start: bool,
     pub hash: String,
+    /// empty for v2-only torrents, missing before qBittorrent v4.4.0
+    #[serde(default)]
+    pub infohash_v1: String,
+    /// empty for v1-only torrents, missing before qBittorrent v4.4.0
+    #[serde(default)]
+    pub infohash_v2: String,
     pub last_activity: u64,
     pub magnet_uri: String,
     pub max_ratio: f64,
@@ -136,6 +148,9 @@ pub struct InfoResponseItem {
     pub ratio: f64,
     pub ratio_limit: f64,
     pub save_path: String,
+    /// in seconds, missing before qBittorrent v4.1.8
+    #[serde(default)]
+    pub seeding_time: i64,
     pub seeding_time_limit: i64,
     pub seen_complete: i64,
     pub seq_dl: bool,
@@ -152,9 +167,16 @@ pub struct InfoResponseItem {
     pub upspeed: i64,
 }
 
+impl InfoResponseItem {
+    /// [`InfoResponseItem::magnet_uri`] parsed, `None` if it isn't a valid magnet link
+    pub fn magnet_link(&self) -> Option<MagnetLink> {
+        self.magnet_uri.parse().ok()
+    }
+}
+
 /// # `/api/v2/torrents/info`
 /// [`InfoResponseItem::state`]
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub enum InfoState {
     Error,
@@ -427,17 +449,28 @@ impl ReannounceForm {
 }
 
 /// # `/api/v2/torrents/add`
+///
+/// every field set is sent as is, [`AddTorrentOptions`] checks them and picks
+/// the fields known by the server
 #[derive(Debug, Clone, Default)]
 pub struct AddMultipart {
     pub urls: Vec<String>,
     pub torrents: Vec<(String, Vec<u8>)>,
     pub savepath: Option<String>,
+    pub download_path: Option<String>,
+    pub use_download_path: Option<bool>,
     pub cookie: Option<String>,
     pub category: Option<String>,
     pub tags: Option<Vec<String>>,
     pub skip_hash_check: Option<bool>,
     pub paused: Option<bool>,
+    /// `paused` since Web API v2.11.0
+    pub stopped: Option<bool>,
+    /// removed in Web API v2.7.0, replaced by `content_layout`
     pub root_folder: Option<bool>,
+    pub content_layout: Option<ContentLayout>,
+    pub stop_condition: Option<StopCondition>,
+    pub add_to_top_of_queue: Option<bool>,
     pub rename: Option<String>,
     pub upload_limit: Option<i64>,
     pub download_limit: Option<i64>,
@@ -482,6 +515,14 @@ impl AddMultipart {
             form = form.text("savepath", savepath.to_owned());
         }
 
+        if let Some(download_path) = &self.download_path {
+            form = form.text("downloadPath", download_path.to_owned());
+        }
+
+        if let Some(use_download_path) = &self.use_download_path {
+            form = form.text("useDownloadPath", use_download_path.to_string());
+        }
+
         if let Some(cookie) = &self.cookie {
             form = form.text("cookie", cookie.to_owned());
         }
@@ -503,10 +544,26 @@ impl AddMultipart {
             form = form.text("paused", paused.to_string());
         }
 
+        if let Some(stopped) = &self.stopped {
+            form = form.text("stopped", stopped.to_string());
+        }
+
         if let Some(root_folder) = &self.root_folder {
             form = form.text("root_folder", root_folder.to_string());
         }
 
+        if let Some(content_layout) = &self.content_layout {
+            form = form.text("contentLayout", content_layout.as_str());
+        }
+
+        if let Some(stop_condition) = &self.stop_condition {
+            form = form.text("stopCondition", stop_condition.as_str());
+        }
+
+        if let Some(add_to_top_of_queue) = &self.add_to_top_of_queue {
+            form = form.text("addToTopOfQueue", add_to_top_of_queue.to_string());
+        }
+
         if let Some(rename) = &self.rename {
             form = form.text("rename", rename.to_owned());
         }
@@ -543,6 +600,491 @@ impl AddMultipart {
     }
 }
 
+/// # `/api/v2/torrents/add`
+///
+/// A torrent to add: an url (magnet link or http url of a torrent file), or the
+/// content of a torrent file, in memory, on disk or read from an [`AsyncRead`]
+pub enum TorrentSource {
+    Url(String),
+    Bytes {
+        /// file name sent to the server
+        name: String,
+        content: Bytes,
+    },
+    Path(PathBuf),
+    Reader {
+        /// file name sent to the server
+        name: String,
+        reader: Box<dyn AsyncRead + Send + Unpin>,
+    },
+}
+
+impl TorrentSource {
+    pub fn url<U: AsRef<str>>(url: U) -> Self {
+        Self::Url(url.as_ref().to_owned())
+    }
+
+    pub fn bytes<N, B>(name: N, content: B) -> Self
+    where
+        N: AsRef<str>,
+        B: Into<Bytes>,
+    {
+        Self::Bytes {
+            name: name.as_ref().to_owned(),
+            content: content.into(),
+        }
+    }
+
+    pub fn path<P: AsRef<Path>>(path: P) -> Self {
+        Self::Path(path.as_ref().to_owned())
+    }
+
+    /// read to the end when added
+    pub fn reader<N, R>(name: N, reader: R) -> Self
+    where
+        N: AsRef<str>,
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        Self::Reader {
+            name: name.as_ref().to_owned(),
+            reader: Box::new(reader),
+        }
+    }
+
+    /// the url, file name or path, to tell the source apart in errors
+    pub fn describe(&self) -> Cow<'_, str> {
+        match self {
+            Self::Url(url) => Cow::Borrowed(url),
+            Self::Bytes { name, .. } | Self::Reader { name, .. } => Cow::Borrowed(name),
+            Self::Path(path) => path.to_string_lossy(),
+        }
+    }
+}
+
+impl From<MagnetLink> for TorrentSource {
+    fn from(magnet: MagnetLink) -> Self {
+        Self::Url(magnet.to_string())
+    }
+}
+
+impl From<&MagnetLink> for TorrentSource {
+    fn from(magnet: &MagnetLink) -> Self {
+        Self::Url(magnet.to_string())
+    }
+}
+
+impl std::fmt::Debug for TorrentSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Url(url) => f.debug_tuple("Url").field(url).finish(),
+            Self::Bytes { name, content } => f
+                .debug_struct("Bytes")
+                .field("name", name)
+                .field("len", &content.len())
+                .finish(),
+            Self::Path(path) => f.debug_tuple("Path").field(path).finish(),
+            Self::Reader { name, .. } => f
+                .debug_struct("Reader")
+                .field("name", name)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+/// # `/api/v2/torrents/add`
+///
+/// What happened to one [`TorrentSource`], see
+/// [`crate::client::QbitApi::torrents_add_outcomes`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddOutcome {
+    /// the torrent was added, with its hash
+    Added(String),
+    /// the torrent was already there, or earlier in the same batch
+    Duplicate(String),
+    /// the source can't be read, isn't a valid torrent, or was rejected by the server
+    Failed(String),
+    /// accepted by the server, but the hash of an url to a torrent file can't be
+    /// known without downloading it
+    Accepted,
+}
+
+impl AddOutcome {
+    /// the hash of an added or duplicate torrent
+    pub fn hash(&self) -> Option<&str> {
+        match self {
+            Self::Added(hash) | Self::Duplicate(hash) => Some(hash),
+            Self::Failed(_) | Self::Accepted => None,
+        }
+    }
+}
+
+/// # `/api/v2/torrents/add`
+/// [`AddMultipart::content_layout`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ContentLayout {
+    Original,
+    /// create a root folder for multi-file torrents
+    Subfolder,
+    /// strip the root folder of multi-file torrents
+    NoSubfolder,
+}
+
+impl ContentLayout {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Original => "Original",
+            Self::Subfolder => "Subfolder",
+            Self::NoSubfolder => "NoSubfolder",
+        }
+    }
+}
+
+/// # `/api/v2/torrents/add`
+/// [`AddMultipart::stop_condition`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum StopCondition {
+    None,
+    MetadataReceived,
+    FilesChecked,
+}
+
+impl StopCondition {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::MetadataReceived => "MetadataReceived",
+            Self::FilesChecked => "FilesChecked",
+        }
+    }
+}
+
+/// Web API versions adding the fields of `torrents/add`
+mod add_since {
+    use super::WebApiVersion;
+
+    pub const CONTENT_LAYOUT: WebApiVersion = WebApiVersion::new(2, 7, 0);
+    pub const DOWNLOAD_PATH: WebApiVersion = WebApiVersion::new(2, 8, 4);
+    pub const STOP_CONDITION: WebApiVersion = WebApiVersion::new(2, 8, 18);
+    pub const ADD_TO_TOP_OF_QUEUE: WebApiVersion = WebApiVersion::new(2, 8, 18);
+    pub const STOPPED: WebApiVersion = WebApiVersion::new(2, 11, 0);
+}
+
+/// # `/api/v2/torrents/add`
+///
+/// Options of adding torrents, checked by [`AddTorrentOptions::validate`]
+///
+/// fields are emitted according to the Web API version of the server: `content_layout`
+/// and `root_folder` are translated into each other, `paused` is sent as `stopped`
+/// since v2.11.0, and fields the server doesn't know yet are left out with a warning.
+/// when the version is unknown, every field is sent, and `paused` as both.
+///
+/// ```
+/// use qbit_api_rs::types::torrents::{AddTorrentOptions, ContentLayout, StopCondition};
+///
+/// let options = AddTorrentOptions::new()
+///     .category("linux")
+///     .tags(["iso"])
+///     .content_layout(ContentLayout::NoSubfolder)
+///     .stop_condition(StopCondition::MetadataReceived)
+///     .add_to_top_of_queue(true);
+/// assert!(options.validate().is_ok());
+///
+/// let options = options.savepath("/downloads").auto_tmm(true);
+/// assert!(options.validate().is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AddTorrentOptions {
+    savepath: Option<String>,
+    download_path: Option<String>,
+    use_download_path: Option<bool>,
+    cookie: Option<String>,
+    category: Option<String>,
+    tags: Vec<String>,
+    skip_checking: Option<bool>,
+    paused: Option<bool>,
+    root_folder: Option<bool>,
+    content_layout: Option<ContentLayout>,
+    stop_condition: Option<StopCondition>,
+    add_to_top_of_queue: Option<bool>,
+    rename: Option<String>,
+    upload_limit: Option<i64>,
+    download_limit: Option<i64>,
+    ratio_limit: Option<f32>,
+    seeding_time_limit: Option<u64>,
+    auto_tmm: Option<bool>,
+    sequential_download: Option<bool>,
+    first_last_piece_prio: Option<bool>,
+    api_version: Option<WebApiVersion>,
+}
+
+impl AddTorrentOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn savepath<P: AsRef<str>>(mut self, savepath: P) -> Self {
+        self.savepath = Some(savepath.as_ref().to_owned());
+        self
+    }
+
+    /// where incomplete torrents are downloaded, used when `use_download_path` is set
+    pub fn download_path<P: AsRef<str>>(mut self, download_path: P) -> Self {
+        self.download_path = Some(download_path.as_ref().to_owned());
+        self
+    }
+
+    pub fn use_download_path(mut self, use_download_path: bool) -> Self {
+        self.use_download_path = Some(use_download_path);
+        self
+    }
+
+    /// cookie sent to download the torrent files of urls
+    pub fn cookie<C: AsRef<str>>(mut self, cookie: C) -> Self {
+        self.cookie = Some(cookie.as_ref().to_owned());
+        self
+    }
+
+    pub fn category<C: AsRef<str>>(mut self, category: C) -> Self {
+        self.category = Some(category.as_ref().to_owned());
+        self
+    }
+
+    pub fn tags<I, T>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        self.tags
+            .extend(tags.into_iter().map(|t| t.as_ref().to_owned()));
+        self
+    }
+
+    pub fn skip_checking(mut self, skip_checking: bool) -> Self {
+        self.skip_checking = Some(skip_checking);
+        self
+    }
+
+    /// add the torrents paused, or stopped since qBittorrent 5.0
+    pub fn paused(mut self, paused: bool) -> Self {
+        self.paused = Some(paused);
+        self
+    }
+
+    /// deprecated by qBittorrent, prefer [`AddTorrentOptions::content_layout`]
+    pub fn root_folder(mut self, root_folder: bool) -> Self {
+        self.root_folder = Some(root_folder);
+        self
+    }
+
+    pub fn content_layout(mut self, content_layout: ContentLayout) -> Self {
+        self.content_layout = Some(content_layout);
+        self
+    }
+
+    pub fn stop_condition(mut self, stop_condition: StopCondition) -> Self {
+        self.stop_condition = Some(stop_condition);
+        self
+    }
+
+    pub fn add_to_top_of_queue(mut self, add_to_top_of_queue: bool) -> Self {
+        self.add_to_top_of_queue = Some(add_to_top_of_queue);
+        self
+    }
+
+    /// name of the torrent, only valid when adding a single torrent
+    pub fn rename<N: AsRef<str>>(mut self, rename: N) -> Self {
+        self.rename = Some(rename.as_ref().to_owned());
+        self
+    }
+
+    /// in bytes/s, `-1` for unlimited
+    pub fn upload_limit(mut self, limit: i64) -> Self {
+        self.upload_limit = Some(limit);
+        self
+    }
+
+    /// in bytes/s, `-1` for unlimited
+    pub fn download_limit(mut self, limit: i64) -> Self {
+        self.download_limit = Some(limit);
+        self
+    }
+
+    /// `-2` for the global limit, `-1` for unlimited
+    pub fn ratio_limit(mut self, limit: f32) -> Self {
+        self.ratio_limit = Some(limit);
+        self
+    }
+
+    /// in minutes
+    pub fn seeding_time_limit(mut self, limit: u64) -> Self {
+        self.seeding_time_limit = Some(limit);
+        self
+    }
+
+    /// let the category decide the save path
+    pub fn auto_tmm(mut self, auto_tmm: bool) -> Self {
+        self.auto_tmm = Some(auto_tmm);
+        self
+    }
+
+    pub fn sequential_download(mut self, sequential_download: bool) -> Self {
+        self.sequential_download = Some(sequential_download);
+        self
+    }
+
+    pub fn first_last_piece_prio(mut self, first_last_piece_prio: bool) -> Self {
+        self.first_last_piece_prio = Some(first_last_piece_prio);
+        self
+    }
+
+    /// The Web API version of the server, so it doesn't have to be requested
+    pub fn api_version(mut self, version: WebApiVersion) -> Self {
+        self.api_version = Some(version);
+        self
+    }
+
+    /// Whether the fields emitted depend on the Web API version, and it isn't set
+    pub fn needs_api_version(&self) -> bool {
+        self.api_version.is_none()
+            && (self.root_folder.is_some()
+                || self.content_layout.is_some()
+                || self.download_path.is_some()
+                || self.use_download_path.is_some()
+                || self.stop_condition.is_some()
+                || self.add_to_top_of_queue.is_some())
+    }
+
+    /// Check for options that conflict with each other
+    pub fn validate(&self) -> Result<(), TypesError> {
+        let err = |msg: &str| Err(TypesError::Other(msg.to_owned()));
+        if self.auto_tmm == Some(true) && self.savepath.is_some() {
+            return err("`savepath` is ignored when `auto_tmm` is enabled");
+        }
+        if self.auto_tmm == Some(true) && self.download_path.is_some() {
+            return err("`download_path` is ignored when `auto_tmm` is enabled");
+        }
+        if self.use_download_path == Some(false) && self.download_path.is_some() {
+            return err("`download_path` is set but `use_download_path` is disabled");
+        }
+        if self.root_folder.is_some() && self.content_layout.is_some() {
+            return err("`root_folder` and `content_layout` can't be set together");
+        }
+        if self
+            .tags
+            .iter()
+            .any(|t| t.contains(',') || t.trim().is_empty())
+        {
+            return err("a tag can't be empty or contain `,`");
+        }
+        if self
+            .ratio_limit
+            .is_some_and(|r| r < 0.0 && r != -1.0 && r != -2.0)
+        {
+            return err("`ratio_limit` must be positive, `-1` or `-2`");
+        }
+
+        Ok(())
+    }
+
+    /// Build the multipart adding `urls` and `torrents`
+    ///
+    /// `version` is the Web API version of the server, [`AddTorrentOptions::api_version`]
+    /// takes precedence over it
+    pub fn to_multipart(
+        &self,
+        urls: Vec<String>,
+        torrents: Vec<(String, Vec<u8>)>,
+        version: Option<WebApiVersion>,
+    ) -> Result<AddMultipart, TypesError> {
+        self.validate()?;
+        if self.rename.is_some() && urls.len() + torrents.len() > 1 {
+            return Err(TypesError::Other(
+                "`rename` is only valid when adding a single torrent".into(),
+            ));
+        }
+
+        let version = self.api_version.or(version);
+        let supports = |since: WebApiVersion, field: &str| match version {
+            Some(version) if version < since => {
+                log::warn!(
+                    "`{}` of torrents/add is ignored, it requires Web API v{} but the server is v{}",
+                    field,
+                    since,
+                    version
+                );
+                false
+            }
+            _ => true,
+        };
+
+        let mut mp = AddMultipart {
+            urls,
+            torrents,
+            savepath: self.savepath.clone(),
+            cookie: self.cookie.clone(),
+            category: self.category.clone(),
+            tags: (!self.tags.is_empty()).then(|| self.tags.clone()),
+            skip_hash_check: self.skip_checking,
+            rename: self.rename.clone(),
+            upload_limit: self.upload_limit,
+            download_limit: self.download_limit,
+            ratio_limit: self.ratio_limit,
+            seeding_time_limit: self.seeding_time_limit,
+            auto_tmm: self.auto_tmm,
+            sequential_download: self.sequential_download,
+            first_last_piece_prio: self.first_last_piece_prio,
+            ..Default::default()
+        };
+
+        match version {
+            Some(version) if version >= add_since::STOPPED => mp.stopped = self.paused,
+            Some(_) => mp.paused = self.paused,
+            None => {
+                mp.paused = self.paused;
+                mp.stopped = self.paused;
+            }
+        }
+
+        match version {
+            Some(version) if version < add_since::CONTENT_LAYOUT => {
+                mp.root_folder = match self.content_layout {
+                    Some(ContentLayout::Subfolder) => Some(true),
+                    Some(ContentLayout::NoSubfolder) => Some(false),
+                    Some(ContentLayout::Original) | None => self.root_folder,
+                };
+            }
+            Some(_) => {
+                mp.content_layout = match self.root_folder {
+                    Some(true) => Some(ContentLayout::Subfolder),
+                    Some(false) => Some(ContentLayout::NoSubfolder),
+                    None => self.content_layout,
+                };
+            }
+            None => {
+                mp.root_folder = self.root_folder;
+                mp.content_layout = self.content_layout;
+            }
+        }
+
+        if (self.download_path.is_some() || self.use_download_path.is_some())
+            && supports(add_since::DOWNLOAD_PATH, "downloadPath")
+        {
+            mp.download_path = self.download_path.clone();
+            mp.use_download_path = self.use_download_path;
+        }
+        if self.stop_condition.is_some() && supports(add_since::STOP_CONDITION, "stopCondition") {
+            mp.stop_condition = self.stop_condition;
+        }
+        if self.add_to_top_of_queue.is_some()
+            && supports(add_since::ADD_TO_TOP_OF_QUEUE, "addToTopOfQueue")
+        {
+            mp.add_to_top_of_queue = self.add_to_top_of_queue;
+        }
+
+        Ok(mp)
+    }
+}
+
 /// # `/api/v2/torrents/addTrackers`
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct AddTrackersForm {
@@ -646,7 +1188,14 @@ impl BottomPrioForm {
 }
 
 /// # `/api/v2/torrents/filePrio`
-// TODO: Implement
+#[derive(Debug, Clone, Serialize)]
+pub struct FilePrioForm {
+    pub hash: String,
+    /// file indexes, which are the `index` in [`FilesResponseItem`]
+    #[serde(serialize_with = "u64_saperated_with_vertical_bar::serialize")]
+    pub id: Vec<u64>,
+    pub priority: FilesPriority,
+}
 
 /// # `/api/v2/torrents/downloadLimit`
 ///
@@ -0,0 +1,93 @@
+//! torrents added from urls, bytes, readers and paths in a single `torrents/add` request
+
+use bytes::Bytes;
+use qbit_api_rs::client::{QbitApi, QbitClient};
+use qbit_api_rs::error::ClientError;
+use qbit_api_rs::types::torrents::{AddTorrentOptions, TorrentSource};
+use wiremock::matchers::{body_string_contains, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const MAGNET: &str = "magnet:?xt=urn:btih:7e2fc0391f2d855affed3b0545927bddd5189bc6";
+
+async fn server() -> (MockServer, QbitClient) {
+    let server = MockServer::start().await;
+    let client =
+        QbitClient::new_with_user_pwd(server.uri().as_str(), "admin", "adminadmin").unwrap();
+    (server, client)
+}
+
+#[tokio::test]
+async fn test_mixed_batch() -> anyhow::Result<()> {
+    let (server, client) = server().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v2/torrents/add"))
+        .and(body_string_contains(MAGNET))
+        .and(body_string_contains(r#"filename="memory.torrent""#))
+        .and(body_string_contains("in memory"))
+        .and(body_string_contains(r#"filename="reader.torrent""#))
+        .and(body_string_contains("from a reader"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("Ok."))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let sources = vec![
+        TorrentSource::url(MAGNET),
+        TorrentSource::bytes("memory.torrent", Bytes::from_static(b"in memory")),
+        TorrentSource::reader("reader.torrent", &b"from a reader"[..]),
+    ];
+    client
+        .torrents_add_sources(sources, &Default::default())
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bytes() -> anyhow::Result<()> {
+    let (server, client) = server().await;
+    Mock::given(path("/api/v2/torrents/add"))
+        .and(body_string_contains(r#"filename="a.torrent""#))
+        .and(body_string_contains(r#"filename="b.torrent""#))
+        .and(body_string_contains("linux"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("Ok."))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let torrents = [
+        ("a.torrent", Bytes::from_static(b"a")),
+        ("b.torrent", Bytes::from_static(b"b")),
+    ];
+    let options = AddTorrentOptions::new().category("linux");
+    client.torrents_add_by_bytes(&torrents, &options).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_error_reports_path() -> anyhow::Result<()> {
+    let (server, client) = server().await;
+    Mock::given(path("/api/v2/torrents/add"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("Ok."))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let missing = "./tests/missing.torrent";
+    let res = client
+        .torrents_add_by_file(&[
+            "./tests/ubuntu-22.04.2-live-server-amd64.iso.torrent",
+            missing,
+        ])
+        .await;
+    match res {
+        Err(ClientError::ReadTorrent { path, source }) => {
+            assert_eq!(path, missing);
+            assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        }
+        res => panic!("expected ReadTorrent: {:?}", res),
+    }
+
+    Ok(())
+}
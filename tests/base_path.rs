@@ -0,0 +1,40 @@
+//! a qBittorrent served under a sub-path, e.g. behind a reverse proxy at `/qbit/`
+
+use qbit_api_rs::client::{QbitApi, QbitClient};
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_sub_path_host() -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    let referer = format!("{}/qbit/", server.uri());
+    let origin = server.uri();
+
+    Mock::given(method("POST"))
+        .and(path("/qbit/api/v2/auth/login"))
+        .and(header("Referer", referer.as_str()))
+        .and(header("Origin", origin.as_str()))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("set-cookie", "SID=session; HttpOnly; path=/qbit")
+                .set_body_string("Ok."),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/qbit/api/v2/app/version"))
+        .and(header("Referer", referer.as_str()))
+        .and(header("Origin", origin.as_str()))
+        .and(header("Cookie", "SID=session"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("v4.6.2"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = QbitClient::new_with_user_pwd(referer.as_str(), "admin", "adminadmin")?;
+    client.auth_login().await?;
+    assert_eq!(client.app_version().await?, "v4.6.2");
+
+    Ok(())
+}
@@ -0,0 +1,140 @@
+//! `QbitCluster` merges reads and routes writes over several `mock::MockServer`
+
+use qbit_api_rs::cluster::QbitCluster;
+use qbit_api_rs::error::ClientError;
+use qbit_api_rs::mock::{MockServer, MockTorrent};
+use qbit_api_rs::types::torrents::InfoState;
+
+const DEBIAN: &str = "8c4adbf9ebe66f1d804fb6a4fb9b74966c3ab609";
+const UBUNTU: &str = "75439d5de343999ab377c617c2c647902956e282";
+const ARCH: &str = "7e2fc0391f2d855affed3b0545927bddd5189bc6";
+
+/// `nas` has debian, `seedbox` has ubuntu
+async fn servers() -> (MockServer, MockServer) {
+    let nas = MockServer::start().await;
+    let mut debian = MockTorrent::new(DEBIAN, "debian.iso");
+    debian.category = "linux".into();
+    debian.dlspeed = 100;
+    nas.add_torrent(debian);
+    nas.add_category("linux", "/nas/linux");
+
+    let seedbox = MockServer::start().await;
+    let mut ubuntu = MockTorrent::new(UBUNTU, "ubuntu.iso");
+    ubuntu.upspeed = 50;
+    seedbox.add_torrent(ubuntu);
+    seedbox.add_category("linux", "/seedbox/linux");
+    seedbox.add_category("movies", "/seedbox/movies");
+
+    (nas, seedbox)
+}
+
+async fn cluster(nas: &MockServer, seedbox: &MockServer) -> QbitCluster {
+    let mut cluster = QbitCluster::new();
+    cluster.insert("nas", nas.client());
+    cluster.insert("seedbox", seedbox.client());
+    assert!(cluster.auth_login().await.is_complete());
+    cluster
+}
+
+#[tokio::test]
+async fn test_aggregated_reads() -> anyhow::Result<()> {
+    let (nas, seedbox) = servers().await;
+    let cluster = cluster(&nas, &seedbox).await;
+    assert_eq!(cluster.names().collect::<Vec<_>>(), ["nas", "seedbox"]);
+
+    let resp = cluster.torrents_info(&Default::default()).await;
+    assert!(resp.is_complete());
+    let torrents: Vec<_> = resp
+        .data
+        .iter()
+        .map(|t| (t.instance.as_str(), t.torrent.hash.as_str()))
+        .collect();
+    assert_eq!(torrents, [("nas", DEBIAN), ("seedbox", UBUNTU)]);
+
+    let resp = cluster.transfer_info().await;
+    assert!(resp.is_complete());
+    assert_eq!(resp.data.dl_info_speed, 100);
+    assert_eq!(resp.data.up_info_speed, 50);
+    assert_eq!(resp.data.instances.len(), 2);
+
+    let resp = cluster.torrents_categories().await;
+    assert_eq!(resp.data["linux"]["nas"].save_path, "/nas/linux");
+    assert_eq!(resp.data["linux"]["seedbox"].save_path, "/seedbox/linux");
+    assert_eq!(resp.data["movies"].len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_partial_results() -> anyhow::Result<()> {
+    let (nas, seedbox) = servers().await;
+    let cluster = cluster(&nas, &seedbox).await;
+
+    seedbox.expire_sessions();
+    let resp = cluster.torrents_info(&Default::default()).await;
+    assert!(!resp.is_complete());
+    assert_eq!(resp.data.len(), 1);
+    assert_eq!(resp.data[0].instance, "nas");
+    assert!(matches!(
+        resp.failures["seedbox"],
+        ClientError::NeedAuthentication
+    ));
+
+    // ubuntu can't be found while seedbox is down
+    let resp = cluster.torrents_pause(&[DEBIAN, UBUNTU]).await;
+    assert_eq!(resp.not_found, [UBUNTU]);
+    assert!(resp.failures.contains_key("seedbox"));
+    assert_eq!(nas.torrent(DEBIAN).unwrap().state, InfoState::PausedDL);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_routed_writes() -> anyhow::Result<()> {
+    let (nas, seedbox) = servers().await;
+    let cluster = cluster(&nas, &seedbox).await;
+
+    let resp = cluster.owners(&[DEBIAN, UBUNTU, ARCH]).await;
+    assert_eq!(resp.data["nas"], [DEBIAN]);
+    assert_eq!(resp.data["seedbox"], [UBUNTU]);
+    assert_eq!(resp.not_found, [ARCH]);
+
+    let resp = cluster.torrents_pause(&[DEBIAN, UBUNTU]).await;
+    assert!(resp.is_complete());
+    assert_eq!(nas.torrent(DEBIAN).unwrap().state, InfoState::PausedDL);
+    assert_eq!(seedbox.torrent(UBUNTU).unwrap().state, InfoState::PausedDL);
+
+    let resp = cluster.torrents_add_tags(&[DEBIAN, UBUNTU], &["iso"]).await;
+    assert!(resp.is_complete());
+    assert_eq!(nas.torrent(DEBIAN).unwrap().tags, ["iso"]);
+    assert_eq!(seedbox.torrent(UBUNTU).unwrap().tags, ["iso"]);
+
+    let resp = cluster.torrents_set_category(&[UBUNTU], "movies").await;
+    assert!(resp.is_complete());
+    assert_eq!(seedbox.torrent(UBUNTU).unwrap().category, "movies");
+
+    // no hashes is no torrent, not every torrent
+    let resp = cluster.torrents_delete(&[] as &[&str], true).await;
+    assert!(resp.is_complete());
+    assert!(nas.torrent(DEBIAN).is_some());
+    assert!(seedbox.torrent(UBUNTU).is_some());
+
+    let resp = cluster.torrents_delete(&[UBUNTU], false).await;
+    assert!(resp.is_complete());
+    assert!(seedbox.torrent(UBUNTU).is_none());
+    assert!(nas.torrent(DEBIAN).is_some());
+
+    // a torrent on both instances is routed to both
+    nas.add_torrent(MockTorrent::new(ARCH, "arch.iso"));
+    seedbox.add_torrent(MockTorrent::new(ARCH, "arch.iso"));
+    let resp = cluster.owners(&[ARCH]).await;
+    assert_eq!(resp.data["nas"], [ARCH]);
+    assert_eq!(resp.data["seedbox"], [ARCH]);
+
+    let resp = cluster.torrents_pause(&[ARCH]).await;
+    assert!(resp.is_complete());
+    assert_eq!(nas.torrent(ARCH).unwrap().state, InfoState::PausedDL);
+    assert_eq!(seedbox.torrent(ARCH).unwrap().state, InfoState::PausedDL);
+
+    Ok(())
+}
@@ -0,0 +1,584 @@
+//! every documented error status of the Web API should become the matching `ClientError`
+
+use qbit_api_rs::client::{QbitApi, QbitClient};
+use qbit_api_rs::error::{ClientError, MAX_BODY_LEN};
+use qbit_api_rs::types::torrents::{FilesPriority, RatioLimit};
+use reqwest::StatusCode;
+use wiremock::matchers::path;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const HASH: &str = "7e2fc0391f2d855affed3b0545927bddd5189bc6";
+
+/// respond `$status` on `$path`, then assert the result of `$call` matches `Err($pattern)`
+macro_rules! assert_error {
+    ($path:literal, $status:literal, |$client:ident| $call:expr, $pattern:pat) => {{
+        let server = MockServer::start().await;
+        Mock::given(path($path))
+            .respond_with(ResponseTemplate::new($status))
+            .mount(&server)
+            .await;
+        let $client =
+            QbitClient::new_with_user_pwd(server.uri().as_str(), "admin", "adminadmin").unwrap();
+        let res = $call.await;
+        assert!(
+            matches!(res, Err($pattern)),
+            "{} {}: {:?}",
+            $path,
+            $status,
+            res
+        );
+    }};
+}
+
+#[tokio::test]
+async fn test_auth_errors() {
+    assert_error!(
+        "/api/v2/auth/login",
+        403,
+        |c| c.auth_login(),
+        ClientError::Authentication
+    );
+    assert_error!(
+        "/api/v2/auth/logout",
+        403,
+        |c| c.auth_logout(),
+        ClientError::NeedAuthentication
+    );
+}
+
+#[tokio::test]
+async fn test_unexpected_status() {
+    assert_error!(
+        "/api/v2/app/version",
+        500,
+        |c| c.app_version(),
+        ClientError::UnexpectedStatus { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/info",
+        502,
+        |c| c.torrents_info(&Default::default()),
+        ClientError::UnexpectedStatus { .. }
+    );
+
+    let server = MockServer::start().await;
+    Mock::given(path("/api/v2/torrents/reannounce"))
+        .respond_with(ResponseTemplate::new(503).set_body_string("x".repeat(1000)))
+        .mount(&server)
+        .await;
+    let client =
+        QbitClient::new_with_user_pwd(server.uri().as_str(), "admin", "adminadmin").unwrap();
+    let err = client.torrents_reannounce(&[HASH]).await.unwrap_err();
+    match &err {
+        ClientError::UnexpectedStatus {
+            method,
+            endpoint,
+            status,
+            body,
+        } => {
+            assert_eq!(method, "POST");
+            assert_eq!(endpoint, "/api/v2/torrents/reannounce");
+            assert_eq!(*status, 503);
+            assert_eq!(body.len(), MAX_BODY_LEN + "...".len());
+        }
+        e => panic!("{:?}", e),
+    }
+    assert!(err
+        .to_string()
+        .starts_with("POST /api/v2/torrents/reannounce responded with 503 Service Unavailable"));
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_parse_error() {
+    let server = MockServer::start().await;
+    Mock::given(path("/api/v2/torrents/tags"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<html>"))
+        .mount(&server)
+        .await;
+    let client =
+        QbitClient::new_with_user_pwd(server.uri().as_str(), "admin", "adminadmin").unwrap();
+    let err = client.torrents_tags().await.unwrap_err();
+    match &err {
+        ClientError::ParseError { endpoint, body, .. } => {
+            assert_eq!(endpoint, "/api/v2/torrents/tags");
+            assert_eq!(body, "<html>");
+        }
+        e => panic!("{:?}", e),
+    }
+    // the serde error is kept as the source
+    let source = std::error::Error::source(&err).unwrap();
+    assert!(source.is::<serde_json::Error>());
+    assert!(!err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_retryable() {
+    let conflict = ClientError::Conflict("exists".into());
+    assert_eq!(conflict.status(), Some(StatusCode::CONFLICT));
+    assert!(!conflict.is_retryable());
+    assert!(!ClientError::NeedAuthentication.is_retryable());
+    assert_eq!(
+        ClientError::Initialize("bad host".into()).to_string(),
+        "Initialize Error: bad host"
+    );
+}
+
+#[tokio::test]
+async fn test_network_error() {
+    // nothing listens on the port once the listener is dropped
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let uri = format!("http://127.0.0.1:{}", port);
+    let client = QbitClient::new_with_user_pwd(uri.as_str(), "admin", "adminadmin").unwrap();
+    let res = client.app_version().await;
+    assert!(
+        matches!(res, Err(ClientError::ReqwestError(_))),
+        "{:?}",
+        res
+    );
+    assert!(res.unwrap_err().is_retryable());
+}
+
+#[tokio::test]
+async fn test_need_authentication() {
+    assert_error!(
+        "/api/v2/app/preferences",
+        403,
+        |c| c.app_preferences(),
+        ClientError::NeedAuthentication
+    );
+    assert_error!(
+        "/api/v2/log/main",
+        403,
+        |c| c.log_main(&Default::default()),
+        ClientError::NeedAuthentication
+    );
+    assert_error!(
+        "/api/v2/sync/maindata",
+        403,
+        |c| c.sync_maindata(&Default::default()),
+        ClientError::NeedAuthentication
+    );
+    assert_error!(
+        "/api/v2/transfer/info",
+        403,
+        |c| c.transfer_info(),
+        ClientError::NeedAuthentication
+    );
+    assert_error!(
+        "/api/v2/torrents/bottomPrio",
+        403,
+        |c| c.torrents_bottom_prio(&[HASH]),
+        ClientError::NeedAuthentication
+    );
+    assert_error!(
+        "/api/v2/torrents/tags",
+        403,
+        |c| c.torrents_tags(),
+        ClientError::NeedAuthentication
+    );
+    assert_error!(
+        "/api/v2/rss/items",
+        403,
+        |c| c.rss_items(false),
+        ClientError::NeedAuthentication
+    );
+    assert_error!(
+        "/api/v2/search/plugins",
+        403,
+        |c| c.search_plugins(),
+        ClientError::NeedAuthentication
+    );
+}
+
+#[tokio::test]
+async fn test_sync_errors() {
+    assert_error!(
+        "/api/v2/sync/torrentPeers",
+        404,
+        |c| c.sync_torrent_peers(&Default::default()),
+        ClientError::TorrentNotFound { .. }
+    );
+}
+
+#[tokio::test]
+async fn test_torrents_not_found() {
+    assert_error!(
+        "/api/v2/torrents/properties",
+        404,
+        |c| c.torrents_properties(HASH),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/trackers",
+        404,
+        |c| c.torrents_trackers(HASH),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/webseeds",
+        404,
+        |c| c.torrents_webseeds(HASH),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/files",
+        404,
+        |c| c.torrents_files(HASH, None),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/pieceStates",
+        404,
+        |c| c.torrents_piece_states(HASH),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/pieceHashes",
+        404,
+        |c| c.torrents_piece_hashes(HASH),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/addTrackers",
+        404,
+        |c| c.torrents_add_trackers(HASH, &["udp://tracker"]),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/editTracker",
+        404,
+        |c| c.torrents_edit_tracker(HASH, "udp://a", "udp://b"),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/removeTrackers",
+        404,
+        |c| c.torrents_remove_trackers(HASH, &["udp://a"]),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/addPeers",
+        404,
+        |c| c.torrents_add_peers(&[HASH], &["1.1.1.1:6881"]),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/increasePrio",
+        404,
+        |c| c.torrents_increase_prio(&[HASH]),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/decreasePrio",
+        404,
+        |c| c.torrents_decrease_prio(&[HASH]),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/topPrio",
+        404,
+        |c| c.torrents_top_prio(&[HASH]),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/bottomPrio",
+        404,
+        |c| c.torrents_bottom_prio(&[HASH]),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/filePrio",
+        404,
+        |c| c.torrents_file_prio(HASH, &[0], FilesPriority::High),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/downloadLimit",
+        404,
+        |c| c.torrents_download_limit(&[HASH]),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/setDownloadLimit",
+        404,
+        |c| c.torrents_set_download_limit(&[HASH], 0),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/setShareLimits",
+        404,
+        |c| c.torrents_set_share_limits(&[HASH], RatioLimit::default(), -2),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/uploadLimit",
+        404,
+        |c| c.torrents_upload_limit(&[HASH]),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/setUploadLimit",
+        404,
+        |c| c.torrents_set_upload_limit(&[HASH], 0),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/setLocation",
+        404,
+        |c| c.torrents_set_location(&[HASH], "/downloads"),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/rename",
+        404,
+        |c| c.torernts_rename(HASH, "name"),
+        ClientError::TorrentNotFound { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/setCategory",
+        404,
+        |c| c.torernts_set_category(&[HASH], "tv"),
+        ClientError::TorrentNotFound { .. }
+    );
+}
+
+#[tokio::test]
+async fn test_torrents_bad_request() {
+    assert_error!(
+        "/api/v2/torrents/editTracker",
+        400,
+        |c| c.torrents_edit_tracker(HASH, "udp://a", "b"),
+        ClientError::BadRequest(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/addPeers",
+        400,
+        |c| c.torrents_add_peers(&[HASH], &["peer"]),
+        ClientError::BadRequest(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/filePrio",
+        400,
+        |c| c.torrents_file_prio(HASH, &[0], FilesPriority::High),
+        ClientError::InvalidFilePriority { priority: 6 }
+    );
+    assert_error!(
+        "/api/v2/torrents/setLocation",
+        400,
+        |c| c.torrents_set_location(&[HASH], ""),
+        ClientError::BadRequest(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/createCategory",
+        400,
+        |c| c.torrents_create_category("", "/tv"),
+        ClientError::BadRequest(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/editCategory",
+        400,
+        |c| c.torrents_edit_category("", "/tv"),
+        ClientError::BadRequest(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/renameFile",
+        400,
+        |c| c.torrents_rename_file(HASH, "a", ""),
+        ClientError::BadRequest(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/renameFolder",
+        400,
+        |c| c.torrents_rename_folder(HASH, "a", ""),
+        ClientError::BadRequest(_)
+    );
+}
+
+#[tokio::test]
+async fn test_torrents_conflict() {
+    assert_error!(
+        "/api/v2/torrents/editTracker",
+        409,
+        |c| c.torrents_edit_tracker(HASH, "udp://a", "udp://b"),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/removeTrackers",
+        409,
+        |c| c.torrents_remove_trackers(HASH, &["udp://a"]),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/increasePrio",
+        409,
+        |c| c.torrents_increase_prio(&[HASH]),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/decreasePrio",
+        409,
+        |c| c.torrents_decrease_prio(&[HASH]),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/topPrio",
+        409,
+        |c| c.torrents_top_prio(&[HASH]),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/bottomPrio",
+        409,
+        |c| c.torrents_bottom_prio(&[HASH]),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/filePrio",
+        409,
+        |c| c.torrents_file_prio(HASH, &[0], FilesPriority::High),
+        ClientError::TorrentMetadataNotReady { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/setLocation",
+        409,
+        |c| c.torrents_set_location(&[HASH], "/root"),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/rename",
+        409,
+        |c| c.torernts_rename(HASH, ""),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/setCategory",
+        409,
+        |c| c.torernts_set_category(&[HASH], "none"),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/createCategory",
+        409,
+        |c| c.torrents_create_category("a/../b", "/tv"),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/editCategory",
+        409,
+        |c| c.torrents_edit_category("a/../b", "/tv"),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/renameFile",
+        409,
+        |c| c.torrents_rename_file(HASH, "a", "b"),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/torrents/renameFolder",
+        409,
+        |c| c.torrents_rename_folder(HASH, "a", "b"),
+        ClientError::Conflict(_)
+    );
+}
+
+#[tokio::test]
+async fn test_torrents_add_errors() {
+    let dir = std::env::temp_dir().join("qbit-api-rs-error-status");
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("invalid.torrent");
+    std::fs::write(&file, b"not a torrent").unwrap();
+
+    assert_error!(
+        "/api/v2/torrents/add",
+        415,
+        |c| c.torrents_add_by_file(&[&file]),
+        ClientError::TorrentFileInvalid { .. }
+    );
+    assert_error!(
+        "/api/v2/torrents/add",
+        403,
+        |c| c
+            .torrents_add_by_url(&["magnet:?xt=urn:btih:7e2fc0391f2d855affed3b0545927bddd5189bc6"]),
+        ClientError::NeedAuthentication
+    );
+}
+
+#[tokio::test]
+async fn test_rss_conflict() {
+    assert_error!(
+        "/api/v2/rss/addFolder",
+        409,
+        |c| c.rss_add_folder("a"),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/rss/addFeed",
+        409,
+        |c| c.rss_add_feed("https://example.com/rss", None::<&str>),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/rss/removeItem",
+        409,
+        |c| c.rss_remove_item("a"),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/rss/moveItem",
+        409,
+        |c| c.rss_move_item("a", "b"),
+        ClientError::Conflict(_)
+    );
+}
+
+#[tokio::test]
+async fn test_search_errors() {
+    assert_error!(
+        "/api/v2/search/start",
+        409,
+        |c| c.search_start("ubuntu", "all", "all"),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/search/stop",
+        404,
+        |c| c.search_stop(1),
+        ClientError::SearchJobNotFound { id: 1 }
+    );
+    assert_error!(
+        "/api/v2/search/status",
+        404,
+        |c| c.search_status(Some(2)),
+        ClientError::SearchJobNotFound { id: 2 }
+    );
+    assert_error!(
+        "/api/v2/search/status",
+        404,
+        |c| c.search_status(None),
+        ClientError::UnexpectedStatus { .. }
+    );
+    assert_error!(
+        "/api/v2/search/results",
+        404,
+        |c| c.search_results(3, None, None),
+        ClientError::SearchJobNotFound { id: 3 }
+    );
+    assert_error!(
+        "/api/v2/search/results",
+        409,
+        |c| c.search_results(3, None, Some(100)),
+        ClientError::Conflict(_)
+    );
+    assert_error!(
+        "/api/v2/search/delete",
+        404,
+        |c| c.search_delete(4),
+        ClientError::SearchJobNotFound { id: 4 }
+    );
+}
@@ -0,0 +1,129 @@
+//! `torrents_info_stream` pages through `torrents/info` of the in-memory `mock::MockServer`
+
+use futures_util::StreamExt;
+use qbit_api_rs::client::QbitApi;
+use qbit_api_rs::client::QbitClient;
+use qbit_api_rs::mock::{MockServer, MockTorrent};
+use qbit_api_rs::types::torrents::InfoQuery;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use wiremock::matchers::path;
+use wiremock::{Mock, ResponseTemplate};
+
+fn hash(i: u64) -> String {
+    format!("{:040x}", i)
+}
+
+/// `count` torrents, added one second apart
+async fn server(count: u64) -> MockServer {
+    let server = MockServer::start().await;
+    for i in 0..count {
+        server.add_torrent(torrent(i));
+    }
+    server
+}
+
+fn torrent(i: u64) -> MockTorrent {
+    let mut torrent = MockTorrent::new(hash(i), format!("torrent {}", i));
+    torrent.added_on = 1_700_000_000 + i;
+    torrent
+}
+
+#[tokio::test]
+async fn test_pages() -> anyhow::Result<()> {
+    let server = server(25).await;
+    let client = server.client();
+    client.auth_login().await?;
+
+    let torrents: Vec<_> = client
+        .torrents_info_stream(&Default::default(), 10)
+        .collect()
+        .await;
+    let hashes: Vec<_> = torrents.into_iter().map(|t| t.unwrap().hash).collect();
+    assert_eq!(hashes, (0..25).map(hash).collect::<Vec<_>>());
+
+    // `offset` and `limit` apply to the whole stream
+    let q = InfoQuery {
+        offset: Some(5),
+        limit: Some(12),
+        ..Default::default()
+    };
+    let torrents: Vec<_> = client.torrents_info_stream(&q, 10).collect().await;
+    let hashes: Vec<_> = torrents.into_iter().map(|t| t.unwrap().hash).collect();
+    assert_eq!(hashes, (5..17).map(hash).collect::<Vec<_>>());
+
+    // a `limit` of 0 is no limit
+    let q = InfoQuery {
+        limit: Some(0),
+        ..Default::default()
+    };
+    assert_eq!(client.torrents_info_stream(&q, 10).count().await, 25);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_changes_mid_iteration() -> anyhow::Result<()> {
+    let server = server(25).await;
+    let client = server.client();
+    client.auth_login().await?;
+
+    let mut stream = client.torrents_info_stream(&Default::default(), 10);
+    let mut hashes = vec![];
+    for _ in 0..10 {
+        hashes.push(stream.next().await.unwrap()?.hash);
+    }
+
+    // shift every torrent left to fetch one place back, and add one at the end
+    server.remove_torrent(&hash(3));
+    server.add_torrent(torrent(100));
+
+    while let Some(torrent) = stream.next().await {
+        hashes.push(torrent?.hash);
+    }
+    let unique: HashSet<_> = hashes.iter().collect();
+    assert_eq!(unique.len(), hashes.len(), "a hash was yielded twice");
+    for i in (0..25).chain([100]) {
+        assert!(unique.contains(&hash(i)), "torrent {} was skipped", i);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_gives_up_on_unstable_list() -> anyhow::Result<()> {
+    // every page is the same, as if the list kept moving under the stream
+    let server = wiremock::MockServer::start().await;
+    let maindata: Value = serde_json::from_str(include_str!("MaindataResponse.json"))?;
+    let (_, item) = maindata["torrents"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .next()
+        .unwrap();
+    let page: Vec<Value> = [hash(1), hash(2)]
+        .into_iter()
+        .map(|h| {
+            let mut item = item.clone();
+            item["hash"] = json!(h);
+            item
+        })
+        .collect();
+    Mock::given(path("/api/v2/torrents/info"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page))
+        .mount(&server)
+        .await;
+    let client = QbitClient::new_with_user_pwd(server.uri().as_str(), "admin", "adminadmin")?;
+
+    let torrents: Vec<_> = client
+        .torrents_info_stream(&Default::default(), 2)
+        .collect()
+        .await;
+    assert_eq!(torrents.len(), 3);
+    assert!(torrents[..2].iter().all(|t| t.is_ok()));
+    assert!(torrents[2].is_err());
+    // a page and the page which moved, on the first pass and after each of the 3 restarts
+    assert_eq!(server.received_requests().await.unwrap().len(), 8);
+
+    Ok(())
+}
@@ -0,0 +1,403 @@
+//! drive `QbitClient` end-to-end against the in-memory `mock::MockServer`
+
+use bytes::Bytes;
+use qbit_api_rs::client::{QbitApi, ReauthPolicy};
+use qbit_api_rs::error::ClientError;
+use qbit_api_rs::magnet::MagnetLink;
+use qbit_api_rs::metainfo::Metainfo;
+use qbit_api_rs::mock::{MockSearchResult, MockServer, MockTorrent};
+use qbit_api_rs::sync::{SyncEvent, SyncState};
+use qbit_api_rs::types::search::SearchStatus;
+use qbit_api_rs::types::torrents::{
+    AddOutcome, AddTorrentOptions, ContentLayout, InfoFilter, InfoQuery, InfoSort, InfoState,
+    TorrentSource,
+};
+
+const DEBIAN: &str = "8c4adbf9ebe66f1d804fb6a4fb9b74966c3ab609";
+const UBUNTU: &str = "75439d5de343999ab377c617c2c647902956e282";
+
+async fn server_with_torrents() -> MockServer {
+    let server = MockServer::start().await;
+    let mut debian = MockTorrent::new(DEBIAN, "debian.iso")
+        .file("debian.iso", 600)
+        .file("README", 400);
+    debian.category = "linux".into();
+    debian.tags = vec!["iso".into()];
+    let mut ubuntu = MockTorrent::new(UBUNTU, "ubuntu.iso").file("ubuntu.iso", 2000);
+    ubuntu.progress = 1.0;
+    ubuntu.state = InfoState::StalledUP;
+    server.add_torrent(debian);
+    server.add_torrent(ubuntu);
+    server
+}
+
+#[tokio::test]
+async fn test_auth() -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    let client = server.client();
+
+    let res = client.app_version().await;
+    assert!(matches!(res, Err(ClientError::NeedAuthentication)));
+
+    client.auth_login().await?;
+    assert_eq!(client.app_version().await?, "v4.6.2");
+    assert_eq!(client.app_webapi_version().await?, "2.9.3");
+
+    client.auth_logout().await?;
+    let res = client.app_version().await;
+    assert!(matches!(res, Err(ClientError::NeedAuthentication)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reauth() -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    let client = server.client().with_reauth_policy(ReauthPolicy::RetryOnce);
+    client.auth_login().await?;
+
+    server.expire_sessions();
+    assert_eq!(client.app_version().await?, "v4.6.2");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_torrents_info() -> anyhow::Result<()> {
+    let server = server_with_torrents().await;
+    let client = server.client();
+    client.auth_login().await?;
+
+    let all = client.torrents_info(&Default::default()).await?;
+    assert_eq!(all.len(), 2);
+
+    let q = InfoQuery {
+        filter: Some(InfoFilter::Completed),
+        ..Default::default()
+    };
+    let completed = client.torrents_info(&q).await?;
+    assert_eq!(completed.len(), 1);
+    assert_eq!(completed[0].hash, UBUNTU);
+    assert_eq!(completed[0].amount_left, 0);
+
+    let q = InfoQuery {
+        category: Some("linux".into()),
+        ..Default::default()
+    };
+    let linux = client.torrents_info(&q).await?;
+    assert_eq!(linux.len(), 1);
+    assert_eq!(linux[0].tags, "iso");
+
+    let q = InfoQuery {
+        sort: Some(InfoSort::Size),
+        reverse: Some(true),
+        limit: Some(1),
+        ..Default::default()
+    };
+    let largest = client.torrents_info(&q).await?;
+    assert_eq!(largest.len(), 1);
+    assert_eq!(largest[0].name, "ubuntu.iso");
+
+    let q = InfoQuery {
+        hashes: Some(vec![DEBIAN.into()]),
+        ..Default::default()
+    };
+    let debian = client.torrents_info(&q).await?;
+    assert_eq!(debian[0].size, 1000);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_torrents_properties_and_files() -> anyhow::Result<()> {
+    let server = server_with_torrents().await;
+    let client = server.client();
+    client.auth_login().await?;
+
+    let properties = client.torrents_properties(UBUNTU).await?;
+    assert_eq!(properties.total_size, 2000);
+
+    let files = client.torrents_files(DEBIAN, None).await?;
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[1].name, "README");
+
+    let res = client.torrents_properties(&"0".repeat(40)).await;
+    assert!(matches!(res, Err(ClientError::TorrentNotFound { .. })));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_torrents_add_and_delete() -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    let client = server.client();
+    client.auth_login().await?;
+
+    client
+        .torrents_add_by_url(&[
+            format!("magnet:?xt=urn:btih:{}&dn=debian", DEBIAN.to_uppercase()),
+            // base32 encoded `UBUNTU`
+            "magnet:?xt=urn:btih:OVBZ2XPDIOMZVM3XYYL4FRSHSAUVNYUC".into(),
+        ])
+        .await?;
+    assert_eq!(server.torrent(DEBIAN).unwrap().name, "debian");
+    assert!(server.torrent(UBUNTU).is_some());
+
+    client.torrents_pause(&[DEBIAN]).await?;
+    assert_eq!(server.torrent(DEBIAN).unwrap().state, InfoState::PausedDL);
+    client.torrents_resume(&[DEBIAN]).await?;
+    assert_eq!(server.torrent(DEBIAN).unwrap().state, InfoState::StalledDL);
+
+    client.torrents_delete(&[DEBIAN], false).await?;
+    assert!(server.torrent(DEBIAN).is_none());
+    assert_eq!(server.torrents().len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_with_options() -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    let client = server.client();
+    client.auth_login().await?;
+
+    let options = AddTorrentOptions::new()
+        .category("linux")
+        .tags(["iso", "debian"])
+        .savepath("/isos")
+        .paused(true)
+        .content_layout(ContentLayout::NoSubfolder);
+    client
+        .torrents_add_by_url_with(&[format!("magnet:?xt=urn:btih:{}", DEBIAN)], &options)
+        .await?;
+    let torrent = server.torrent(DEBIAN).unwrap();
+    assert_eq!(torrent.category, "linux");
+    assert_eq!(torrent.tags, ["iso", "debian"]);
+    assert_eq!(torrent.save_path, "/isos");
+    assert_eq!(torrent.state, InfoState::PausedDL);
+
+    let invalid = options.auto_tmm(true);
+    assert!(matches!(
+        client
+            .torrents_add_by_url_with(&[format!("magnet:?xt=urn:btih:{}", UBUNTU)], &invalid)
+            .await,
+        Err(ClientError::InvalidMultipart(_))
+    ));
+    assert!(server.torrent(UBUNTU).is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_outcomes() -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    server.add_torrent(MockTorrent::new(DEBIAN, "debian"));
+    let client = server.client();
+    client.auth_login().await?;
+
+    let sources = vec![
+        TorrentSource::url(format!("magnet:?xt=urn:btih:{}", DEBIAN)),
+        // base32 encoded `UBUNTU`
+        TorrentSource::url("magnet:?xt=urn:btih:OVBZ2XPDIOMZVM3XYYL4FRSHSAUVNYUC&dn=ubuntu"),
+        TorrentSource::url(format!("magnet:?xt=urn:btih:{}", UBUNTU)),
+        TorrentSource::bytes("bad.torrent", &b"not bencode"[..]),
+        TorrentSource::path("./tests/missing.torrent"),
+        TorrentSource::url("https://example.com/debian.torrent"),
+    ];
+    let outcomes = client
+        .torrents_add_outcomes(sources, &Default::default(), true)
+        .await?;
+    assert_eq!(outcomes.len(), 6);
+    assert_eq!(outcomes[0], AddOutcome::Duplicate(DEBIAN.into()));
+    assert_eq!(outcomes[1], AddOutcome::Added(UBUNTU.into()));
+    assert_eq!(outcomes[2], AddOutcome::Duplicate(UBUNTU.into()));
+    assert!(matches!(&outcomes[3], AddOutcome::Failed(reason) if reason.contains("bad.torrent")));
+    assert!(
+        matches!(&outcomes[4], AddOutcome::Failed(reason) if reason.contains("missing.torrent"))
+    );
+    assert_eq!(outcomes[5], AddOutcome::Accepted);
+    assert_eq!(server.torrent(UBUNTU).unwrap().name, "ubuntu");
+
+    let hash = "a585051959d4e06e71da2f4306547a08348e5d34";
+    let sources = vec![TorrentSource::url(format!("magnet:?xt=urn:btih:{}", hash))];
+    let outcomes = client
+        .torrents_add_outcomes(sources, &Default::default(), false)
+        .await?;
+    assert_eq!(outcomes, [AddOutcome::Added(hash.into())]);
+    assert!(server.torrent(hash).is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_torrent_file() -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    let client = server.client();
+    client.auth_login().await?;
+
+    let path = "./tests/ubuntu-22.04.2-live-server-amd64.iso.torrent";
+    let metainfo = Metainfo::from_bytes(&std::fs::read(path)?)?;
+    let sources = vec![TorrentSource::path(path)];
+    let outcomes = client
+        .torrents_add_outcomes(sources, &Default::default(), true)
+        .await?;
+    assert_eq!(outcomes, [AddOutcome::Added(metainfo.hash().into())]);
+
+    let files = client.torrents_files(metainfo.hash(), None).await?;
+    assert!(metainfo.same_files(&files));
+    assert_eq!(server.torrent(metainfo.hash()).unwrap().name, metainfo.name);
+
+    let invalid = [("invalid.torrent", Bytes::from_static(b"d4:infoe"))];
+    assert!(matches!(
+        client
+            .torrents_add_by_bytes(&invalid, &Default::default())
+            .await,
+        Err(ClientError::TorrentFileInvalid { .. })
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_magnets() -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    let client = server.client();
+    client.auth_login().await?;
+
+    let magnets: Vec<MagnetLink> = [
+        format!("magnet:?xt=urn:btih:{}&dn=debian", DEBIAN),
+        format!(
+            "magnet:?xt=urn:btih:{}&tr=udp%3A%2F%2Fa%3A1",
+            DEBIAN.to_uppercase()
+        ),
+        // base32 encoded `UBUNTU`
+        "magnet:?xt=urn:btih:OVBZ2XPDIOMZVM3XYYL4FRSHSAUVNYUC&dn=ubuntu".into(),
+    ]
+    .iter()
+    .map(|m| m.parse())
+    .collect::<Result<_, _>>()?;
+    assert!(magnets[0].same_torrent(&magnets[1]));
+    let mut unique: Vec<MagnetLink> = vec![];
+    for m in magnets {
+        if !unique.iter().any(|u| u.same_torrent(&m)) {
+            unique.push(m.with_tracker("udp://tracker.example.com:1337"));
+        }
+    }
+    client
+        .torrents_add_magnets(&unique, &Default::default())
+        .await?;
+
+    let torrents = client.torrents_info(&Default::default()).await?;
+    assert_eq!(torrents.len(), 2);
+    for m in unique.iter() {
+        let torrent = torrents.iter().find(|t| m.matches(t)).unwrap();
+        assert_eq!(torrent.name, m.name().unwrap());
+        assert!(torrent.magnet_link().unwrap().same_torrent(m));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_categories_and_tags() -> anyhow::Result<()> {
+    let server = server_with_torrents().await;
+    let client = server.client();
+    client.auth_login().await?;
+
+    client.torrents_create_category("movies", "/movies").await?;
+    let res = client.torrents_create_category("movies", "/movies").await;
+    assert!(matches!(res, Err(ClientError::Conflict(_))));
+    let categories = client.torrents_categories().await?;
+    assert_eq!(categories["movies"].save_path, "/movies");
+    assert!(categories.contains_key("linux"));
+
+    client.torernts_set_category(&[UBUNTU], "movies").await?;
+    assert_eq!(server.torrent(UBUNTU).unwrap().category, "movies");
+    let res = client.torernts_set_category(&[UBUNTU], "missing").await;
+    assert!(matches!(res, Err(ClientError::Conflict(_))));
+
+    client.torrents_add_tags(&[UBUNTU], &["new", "iso"]).await?;
+    assert_eq!(client.torrents_tags().await?, vec!["iso", "new"]);
+    client.torrents_delete_tags(&["iso"]).await?;
+    assert_eq!(server.torrent(UBUNTU).unwrap().tags, vec!["new"]);
+    assert!(server.torrent(DEBIAN).unwrap().tags.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sync_maindata() -> anyhow::Result<()> {
+    let server = server_with_torrents().await;
+    let client = server.client();
+    client.auth_login().await?;
+
+    let mut state = SyncState::new();
+    let events = state.sync(&client).await?;
+    assert_eq!(state.torrents().len(), 2);
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, SyncEvent::TorrentAdded { hash, .. } if hash == DEBIAN)));
+
+    // nothing changed, so the partial update is empty
+    let rid = state.rid();
+    let resp = client
+        .sync_maindata(&qbit_api_rs::types::sync::MaindataQuery { rid })
+        .await?;
+    assert!(resp.torrents.is_none());
+    assert_eq!(resp.full_update, None);
+
+    server.update_torrent(DEBIAN, |t| t.progress = 0.5);
+    server.remove_torrent(UBUNTU);
+    let events = state.sync(&client).await?;
+    assert!(events.iter().any(|e| matches!(
+        e,
+        SyncEvent::ProgressChanged { hash, new, .. } if hash == DEBIAN && *new == 0.5
+    )));
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, SyncEvent::TorrentRemoved { hash } if hash == UBUNTU)));
+    assert_eq!(state.torrents().len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search() -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    server.add_search_result(MockSearchResult {
+        file_name: "Debian 12".into(),
+        file_url: format!("magnet:?xt=urn:btih:{}", DEBIAN),
+        ..Default::default()
+    });
+    server.add_search_result(MockSearchResult {
+        file_name: "Ubuntu 22.04".into(),
+        ..Default::default()
+    });
+    let client = server.client();
+    client.auth_login().await?;
+
+    assert_eq!(client.search_plugins().await?.len(), 1);
+
+    let job = client.search_start("debian", "all", "all").await?;
+    let id = job.id();
+    let status = client.search_status(Some(id)).await?;
+    assert_eq!(status[0].status, SearchStatus::Running);
+    let results = client.search_results(id, None, None).await?;
+    assert_eq!(results.status, SearchStatus::Stopped);
+    assert_eq!(results.total, 1);
+    assert_eq!(results.results[0].file_name, "Debian 12");
+
+    // dropped jobs are stopped, keep them running
+    let mut jobs = vec![];
+    for _ in 0..5 {
+        jobs.push(client.search_start("ubuntu", "all", "all").await?);
+    }
+    let res = client.search_start("ubuntu", "all", "all").await;
+    assert!(matches!(res, Err(ClientError::Conflict(_))));
+
+    client.search_delete(id).await?;
+    let res = client.search_status(Some(id)).await;
+    assert!(matches!(res, Err(ClientError::SearchJobNotFound { .. })));
+
+    Ok(())
+}
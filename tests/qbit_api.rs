@@ -0,0 +1,93 @@
+//! code written against `QbitApi` works with a fake or a middleware in place of `QbitClient`
+
+use async_trait::async_trait;
+use qbit_api_rs::api::Endpoint;
+use qbit_api_rs::client::QbitApi;
+use qbit_api_rs::error::ClientError;
+use qbit_api_rs::mock::{MockServer, MockTorrent};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// business logic under test
+async fn describe<A: QbitApi>(api: &A) -> Result<String, ClientError> {
+    let version = api.app_version().await?;
+    let tags = api.torrents_tags().await?;
+    Ok(format!("{} [{}]", version, tags.join(",")))
+}
+
+/// answers canned json by path, without any http
+struct Fake;
+
+#[async_trait]
+impl QbitApi for Fake {
+    async fn execute<E>(&self, endpoint: &E) -> Result<E::Response, ClientError>
+    where
+        E: Endpoint + Sync,
+    {
+        let path = endpoint.relative_path();
+        let body = match path.as_ref() {
+            "/api/v2/app/version" => json!("v4.6.2"),
+            "/api/v2/torrents/tags" => json!(["linux", "iso"]),
+            _ => return Err(ClientError::Other(format!("{} is not faked", path))),
+        };
+        serde_json::from_value(body.clone()).map_err(|e| ClientError::ParseError {
+            endpoint: path.into_owned(),
+            body: body.to_string(),
+            source: Box::new(e),
+        })
+    }
+
+    async fn auth_login(&self) -> Result<(), ClientError> {
+        Ok(())
+    }
+}
+
+/// counts the requests sent through the inner api
+struct Counting<A> {
+    inner: A,
+    requests: AtomicUsize,
+}
+
+#[async_trait]
+impl<A: QbitApi> QbitApi for Counting<A> {
+    async fn execute<E>(&self, endpoint: &E) -> Result<E::Response, ClientError>
+    where
+        E: Endpoint + Sync,
+    {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.inner.execute(endpoint).await
+    }
+
+    async fn auth_login(&self) -> Result<(), ClientError> {
+        self.inner.auth_login().await
+    }
+}
+
+#[tokio::test]
+async fn test_fake() -> anyhow::Result<()> {
+    assert_eq!(describe(&Fake).await?, "v4.6.2 [linux,iso]");
+    assert!(matches!(
+        Fake.torrents_info(&Default::default()).await,
+        Err(ClientError::Other(_))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_middleware() -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    let mut torrent = MockTorrent::new("8c4adbf9ebe66f1d804fb6a4fb9b74966c3ab609", "debian");
+    torrent.tags = vec!["linux".into()];
+    server.add_torrent(torrent);
+
+    let api = Counting {
+        inner: server.client(),
+        requests: AtomicUsize::new(0),
+    };
+    api.auth_login().await?;
+    assert_eq!(describe(&api).await?, "v4.6.2 [linux]");
+    assert_eq!(api.requests.load(Ordering::Relaxed), 2);
+
+    Ok(())
+}
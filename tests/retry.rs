@@ -0,0 +1,117 @@
+//! transient failures are retried according to the `RetryPolicy`
+
+use qbit_api_rs::client::{QbitApi, QbitClient, RetryPolicy};
+use qbit_api_rs::error::ClientError;
+use qbit_api_rs::types::sync::MaindataQuery;
+use std::time::Duration;
+use wiremock::matchers::path;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const HASH: &str = "7e2fc0391f2d855affed3b0545927bddd5189bc6";
+
+fn fast() -> RetryPolicy {
+    RetryPolicy {
+        initial_backoff: Duration::from_millis(1),
+        ..Default::default()
+    }
+}
+
+/// respond `status` on `p` for `failures` times, then `200` with `body`
+async fn flaky(p: &str, status: u16, failures: u64, body: &str) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(path(p))
+        .respond_with(ResponseTemplate::new(status))
+        .up_to_n_times(failures)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(path(p))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn client_with(server: &MockServer, policy: RetryPolicy) -> QbitClient {
+    QbitClient::builder(server.uri().as_str(), "admin", "adminadmin")
+        .retry_policy(policy)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_retry_get() {
+    let server = flaky("/api/v2/app/version", 502, 2, "v4.6.2").await;
+    let client = client_with(&server, fast());
+    assert_eq!(client.app_version().await.unwrap(), "v4.6.2");
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_give_up_after_max_attempts() {
+    let server = flaky("/api/v2/app/version", 503, 3, "v4.6.2").await;
+    let client = client_with(&server, fast());
+    let res = client.app_version().await;
+    assert!(matches!(res, Err(ClientError::UnexpectedStatus { .. })));
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_no_retry_for_fatal_errors() {
+    let server = flaky("/api/v2/torrents/properties", 404, 1, "{}").await;
+    let client = client_with(&server, fast());
+    let res = client.torrents_properties(HASH).await;
+    assert!(matches!(res, Err(ClientError::TorrentNotFound { .. })));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_retry_read_only_post() {
+    let server = flaky("/api/v2/sync/maindata", 502, 2, r#"{"rid":1}"#).await;
+    let client = client_with(&server, fast());
+    let maindata = client
+        .sync_maindata(&MaindataQuery { rid: 0 })
+        .await
+        .unwrap();
+    assert_eq!(maindata.rid, 1);
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_post_opt_in() {
+    let server = flaky("/api/v2/torrents/delete", 503, 1, "").await;
+    let client = client_with(&server, fast());
+    let res = client.torrents_delete(&[HASH], false).await;
+    assert!(matches!(res, Err(ClientError::UnexpectedStatus { .. })));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+    let server = flaky("/api/v2/torrents/delete", 503, 1, "").await;
+    let policy = RetryPolicy {
+        retry_non_idempotent: true,
+        ..fast()
+    };
+    let client = client_with(&server, policy);
+    client.torrents_delete(&[HASH], false).await.unwrap();
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_custom_retry_on() {
+    // retry conflicts too, and nothing else
+    let server = flaky("/api/v2/torrents/createCategory", 409, 1, "").await;
+    let policy = RetryPolicy {
+        retry_non_idempotent: true,
+        retry_on: |e| matches!(e, ClientError::Conflict(_)),
+        ..fast()
+    };
+    let client = client_with(&server, policy);
+    client
+        .torrents_create_category("linux", "/linux")
+        .await
+        .unwrap();
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+    let server = flaky("/api/v2/app/version", 502, 1, "v4.6.2").await;
+    let client = client_with(&server, RetryPolicy::never());
+    assert!(client.app_version().await.is_err());
+}
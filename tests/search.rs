@@ -0,0 +1,170 @@
+//! `SearchJob` against the in-memory `mock::MockServer`
+
+use futures_util::StreamExt;
+use qbit_api_rs::client::QbitApi;
+use qbit_api_rs::error::ClientError;
+use qbit_api_rs::mock::{MockSearchResult, MockServer};
+use qbit_api_rs::search::{add_result_to_client, SearchAggregator};
+use qbit_api_rs::types::search::{PluginSelection, SearchCategory, SearchStatus};
+use qbit_api_rs::types::torrents::AddMultipart;
+use std::time::Duration;
+
+fn result(name: &str) -> MockSearchResult {
+    MockSearchResult {
+        file_name: name.into(),
+        ..Default::default()
+    }
+}
+
+/// let the tasks spawned on drop run
+async fn settle() {
+    tokio::time::sleep(Duration::from_millis(50)).await;
+}
+
+#[tokio::test]
+async fn test_results_until_stopped() -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    server.set_search_polls(6);
+    server.add_search_result(result("Debian 11"));
+    let client = server.client();
+    client.auth_login().await?;
+
+    let mut job = client
+        .search_start("debian", "all", "all")
+        .await?
+        .with_poll_interval(Duration::from_millis(1));
+    assert_eq!(job.status().await?, SearchStatus::Running);
+
+    let mut results = job.results();
+    assert_eq!(results.next().await.unwrap()?.file_name, "Debian 11");
+    // found while the job is running
+    server.add_search_result(result("Debian 12"));
+    assert_eq!(results.next().await.unwrap()?.file_name, "Debian 12");
+    assert!(results.next().await.is_none());
+    drop(results);
+
+    assert_eq!(job.status().await?, SearchStatus::Stopped);
+    // nothing new is yielded twice
+    assert!(job.results().next().await.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stop_on_drop() -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    server.set_search_polls(100);
+    let client = server.client();
+    client.auth_login().await?;
+
+    let job = client.search_start("debian", "all", "all").await?;
+    let id = job.id();
+    assert_eq!(server.search_running(id), Some(true));
+    drop(job);
+    settle().await;
+    assert_eq!(server.search_running(id), Some(false));
+
+    let job = client
+        .search_start("debian", "all", "all")
+        .await?
+        .delete_on_drop(true);
+    let id = job.id();
+    drop(job);
+    settle().await;
+    assert_eq!(server.search_running(id), None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cancel() -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    server.set_search_polls(100);
+    let client = server.client();
+    client.auth_login().await?;
+
+    let job = client.search_start("debian", "all", "all").await?;
+    let id = job.id();
+    job.cancel().await?;
+    assert_eq!(server.search_running(id), Some(false));
+
+    let mut job = client.search_start("debian", "all", "all").await?;
+    job.stop().await?;
+    assert_eq!(job.status().await?, SearchStatus::Stopped);
+    let id = job.id();
+    job.delete().await?;
+    assert_eq!(server.search_running(id), None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_start_checked() -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    let client = server.client();
+    client.auth_login().await?;
+
+    let plugins = PluginSelection::Named(vec!["mock".into()]);
+    let job = client
+        .search_start_checked("debian", plugins, SearchCategory::All)
+        .await?;
+    job.delete().await?;
+
+    let res = client.search_start_checked("debian", "mokc", "all").await;
+    assert!(matches!(res, Err(ClientError::InvalidSearch(_))));
+    let res = client
+        .search_start_checked("debian", PluginSelection::Enabled, SearchCategory::Movies)
+        .await;
+    assert!(matches!(res, Err(ClientError::InvalidSearch(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_aggregate_and_add() -> anyhow::Result<()> {
+    const DEBIAN: &str = "8c4adbf9ebe66f1d804fb6a4fb9b74966c3ab609";
+    let server = MockServer::start().await;
+    server.add_search_result(MockSearchResult {
+        file_name: "Debian 12.0 amd64".into(),
+        file_url: "https://a.example/debian.torrent".into(),
+        file_size: 600.0,
+        nb_seeders: 3,
+        ..Default::default()
+    });
+    server.add_search_result(MockSearchResult {
+        file_name: "debian.12.0.AMD64".into(),
+        file_url: format!("magnet:?xt=urn:btih:{}", DEBIAN),
+        file_size: 600.0,
+        nb_seeders: 40,
+        ..Default::default()
+    });
+    server.add_search_result(MockSearchResult {
+        file_name: "Debian 11".into(),
+        nb_seeders: 10,
+        ..Default::default()
+    });
+    let client = server.client();
+    client.auth_login().await?;
+
+    let mut job = client.search_start("debian", "all", "all").await?;
+    let mut aggregator = SearchAggregator::new();
+    let mut results = job.results();
+    while let Some(result) = results.next().await {
+        aggregator.push(result?);
+    }
+    drop(results);
+
+    let ranked = aggregator.into_ranked();
+    assert_eq!(ranked.len(), 2);
+    assert_eq!(ranked[0].sources.len(), 2);
+    assert_eq!(ranked[0].result.nb_seeders, 40);
+
+    let options = AddMultipart {
+        category: Some("linux".into()),
+        ..Default::default()
+    };
+    add_result_to_client(&client, &ranked[0].result, options).await?;
+    assert_eq!(server.torrent(DEBIAN).unwrap().category, "linux");
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! `TorrentSelector` against the in-memory `mock::MockServer`
+
+use qbit_api_rs::client::QbitApi;
+use qbit_api_rs::mock::{MockServer, MockTorrent};
+use qbit_api_rs::selector::{Cmp, TorrentSelector as S};
+use qbit_api_rs::types::torrents::{InfoFilter, InfoState};
+use std::time::Duration;
+
+const DEBIAN: &str = "8c4adbf9ebe66f1d804fb6a4fb9b74966c3ab609";
+const UBUNTU: &str = "75439d5de343999ab377c617c2c647902956e282";
+const ARCH: &str = "7e2fc0391f2d855affed3b0545927bddd5189bc6";
+
+const WEEK: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// three seeding torrents, only ubuntu is old, well seeded and from `foo` without `keep`
+async fn server() -> MockServer {
+    let server = MockServer::start().await;
+    for (hash, name, category, ratio, days, tags) in [
+        (DEBIAN, "debian", "tv", 3.0, 10, vec!["keep"]),
+        (UBUNTU, "ubuntu", "movies", 2.5, 8, vec![]),
+        (ARCH, "arch", "movies", 1.0, 30, vec![]),
+    ] {
+        let mut torrent = MockTorrent::new(hash, name);
+        torrent.category = category.into();
+        torrent.ratio = ratio;
+        torrent.seeding_time = days * 24 * 3600;
+        torrent.tracker = "https://tracker.foo.org/announce".into();
+        torrent.tags = tags.into_iter().map(Into::into).collect();
+        torrent.progress = 1.0;
+        torrent.state = InfoState::StalledUP;
+        server.add_torrent(torrent);
+    }
+    server
+}
+
+fn stale() -> S {
+    S::category_in(["tv", "movies"])
+        & S::ratio(Cmp::Gt, 2.0)
+        & S::seeding_time(Cmp::Gt, WEEK)
+        & S::tracker_contains("FOO")
+        & !S::tag("keep")
+}
+
+#[tokio::test]
+async fn test_select() -> anyhow::Result<()> {
+    let server = server().await;
+    let client = server.client();
+    client.auth_login().await?;
+
+    let torrents = client.torrents_select(&stale()).await?;
+    let hashes: Vec<_> = torrents.iter().map(|t| t.hash.as_str()).collect();
+    assert_eq!(hashes, [UBUNTU]);
+
+    let selector = S::category("movies") & S::name_contains("ARCH") | S::tag("keep");
+    let mut torrents = client.torrents_select(&selector).await?;
+    torrents.sort_by(|a, b| a.name.cmp(&b.name));
+    let names: Vec<_> = torrents.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["arch", "debian"]);
+
+    let selector = S::filter(InfoFilter::Seeding) & S::seeding_time(Cmp::Ge, WEEK * 4);
+    let torrents = client.torrents_select(&selector).await?;
+    assert_eq!(torrents.len(), 1);
+    assert_eq!(torrents[0].hash, ARCH);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_operations() -> anyhow::Result<()> {
+    let server = server().await;
+    let client = server.client();
+    client.auth_login().await?;
+
+    let stale = stale();
+    client.torrents_add_tags(&stale, &["stale"]).await?;
+    assert_eq!(server.torrent(UBUNTU).unwrap().tags, ["stale"]);
+    assert!(!server.torrent(ARCH).unwrap().tags.contains(&"stale".into()));
+
+    client.torrents_pause(S::tag("stale")).await?;
+    assert_eq!(server.torrent(UBUNTU).unwrap().state, InfoState::PausedUP);
+    assert_eq!(server.torrent(DEBIAN).unwrap().state, InfoState::StalledUP);
+
+    // a selector matching nothing is not an error
+    client.torrents_resume(S::tag("missing")).await?;
+
+    client.torrents_delete(stale, false).await?;
+    assert!(server.torrent(UBUNTU).is_none());
+    assert_eq!(server.torrents().len(), 2);
+
+    // an empty hash list narrows to nothing, it is not sent as `hashes=` meaning every torrent
+    let nothing = S::hashes(Vec::<String>::new()) & S::category("movies");
+    assert!(client.torrents_select(&nothing).await?.is_empty());
+    client.torrents_delete(nothing, false).await?;
+    assert_eq!(server.torrents().len(), 2);
+
+    // hashes keep working
+    client.torrents_delete(&[ARCH], false).await?;
+    client.torrents_delete(S::All, false).await?;
+    assert!(server.torrents().is_empty());
+
+    Ok(())
+}